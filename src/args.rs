@@ -1,19 +1,217 @@
-use clap::Parser;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+use crate::visualize::FrameFormat;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    #[command(flatten)]
+    pub run: RunArgs,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct RunArgs {
+    /// year of the puzzle
+    #[arg(short, long, env = "AOC_YEAR", default_value_t = 2023)]
+    pub year: u32,
+
     /// day to solve
-    pub day: u32,
+    #[arg(required_unless_present = "all")]
+    pub day: Option<u32>,
+
+    /// solve every day of the year
+    #[arg(short, long, conflicts_with_all = ["day", "input"])]
+    pub all: bool,
+
+    /// number of cases to run in parallel
+    #[arg(short, long, default_value_t = 1)]
+    pub jobs: usize,
 
     /// filter on the input file
     pub input_filter: Option<String>,
 
-    /// dump the input
+    /// input file or directory to run on instead of the day inputs, - for stdin
     #[arg(short, long)]
-    pub dump_input: bool,
+    pub input: Option<PathBuf>,
+
+    /// root directory of the inputs
+    #[arg(long, env = "AOC_INPUT_DIR", default_value = "input")]
+    pub input_dir: PathBuf,
+
+    /// dump the parsed input, as a readable summary or the full Debug output
+    #[arg(
+        short,
+        long,
+        value_enum,
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "summary"
+    )]
+    pub dump_input: Option<DumpMode>,
+
+    /// write each dump to a file in this directory instead of the report
+    #[arg(long, requires = "dump_input")]
+    pub dump_dir: Option<PathBuf>,
 
     /// store output on .ans file
     #[arg(short, long)]
     pub store_output: bool,
+
+    /// only solve this part
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=2))]
+    pub part: Option<u32>,
+
+    /// seconds after which parsing or a part is abandoned, 0 to wait forever
+    #[arg(short, long, default_value_t = 60)]
+    pub timeout: u64,
+
+    /// directory to write the frames of the days that can be visualized, - for the terminal
+    #[arg(long)]
+    pub visualize: Option<PathBuf>,
+
+    /// image format of the frames written with --visualize
+    #[arg(long, value_enum, default_value_t = FrameFormat::Gif)]
+    pub frame_format: FrameFormat,
+
+    /// show the debug messages and counters of the solvers, -vv for the trace messages too
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+
+    /// only show the messages of these days, comma separated
+    #[arg(long, value_delimiter = ',', requires = "verbose")]
+    pub log_days: Vec<u32>,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DumpMode {
+    /// the summary of the day, see `AoCProblem::summary`
+    Summary,
+    /// the whole `{:#?}` output
+    Full,
+}
+
+impl RunArgs {
+    pub fn runs_part(&self, part: u32) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+
+    /// Verbosity of the messages recorded for `day`.
+    pub fn verbosity(&self, day: u32) -> u8 {
+        if self.log_days.is_empty() || self.log_days.contains(&day) {
+            self.verbose
+        } else {
+            0
+        }
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        (self.timeout > 0).then(|| Duration::from_secs(self.timeout))
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// submit the answer of a part
+    Submit(SubmitArgs),
+    /// create the source and input files for a new day
+    New(NewArgs),
+    /// rerun the cases of a day every time its inputs change
+    Watch(WatchArgs),
+    /// run a day on random inputs of increasing sizes
+    Stress(StressArgs),
+}
+
+#[derive(clap::Args, Debug)]
+pub struct SubmitArgs {
+    /// year of the puzzle
+    #[arg(short, long, env = "AOC_YEAR", default_value_t = 2023)]
+    pub year: u32,
+
+    /// day of the answer
+    pub day: u32,
+
+    /// part of the answer (1 or 2)
+    #[arg(value_parser = clap::value_parser!(u32).range(1..=2))]
+    pub part: u32,
+
+    /// answer to submit, defaults to the one stored in input.ans
+    pub answer: Option<String>,
+
+    /// base URL of the Advent of Code compatible server
+    #[arg(long, env = "AOC_ENDPOINT", default_value = "https://adventofcode.com")]
+    pub endpoint: String,
+
+    /// session cookie used to authenticate
+    #[arg(long, env = "AOC_SESSION", hide_env_values = true)]
+    pub session: String,
+
+    /// submit even if the answer is known to be wrong
+    #[arg(short, long)]
+    pub force: bool,
+
+    /// root directory of the inputs
+    #[arg(long, env = "AOC_INPUT_DIR", default_value = "input")]
+    pub input_dir: PathBuf,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct NewArgs {
+    /// year of the puzzle
+    #[arg(short, long, env = "AOC_YEAR", default_value_t = 2023)]
+    pub year: u32,
+
+    /// day to create
+    #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+    pub day: u32,
+
+    /// also create a pest grammar for the input
+    #[arg(short, long)]
+    pub pest: bool,
+
+    /// overwrite existing files
+    #[arg(short, long)]
+    pub force: bool,
+
+    /// root directory of the inputs
+    #[arg(long, env = "AOC_INPUT_DIR", default_value = "input")]
+    pub input_dir: PathBuf,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct WatchArgs {
+    #[command(flatten)]
+    pub run: RunArgs,
+
+    /// also watch the sources, rebuilding and restarting on change
+    #[arg(short, long)]
+    pub rebuild: bool,
+
+    /// milliseconds between checks for changes
+    #[arg(long, default_value_t = 500)]
+    pub interval: u64,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct StressArgs {
+    #[command(flatten)]
+    pub run: RunArgs,
+
+    /// sizes of the generated inputs, comma separated
+    #[arg(long, value_delimiter = ',', default_values_t = [10, 100, 1000])]
+    pub sizes: Vec<usize>,
+
+    /// number of inputs generated for each size
+    #[arg(short, long, default_value_t = 5)]
+    pub count: u64,
+
+    /// seed of the first input of each size, the next ones use the following seeds
+    #[arg(long, default_value_t = 0)]
+    pub seed: u64,
 }
@@ -0,0 +1,92 @@
+//! Randomness for the inputs of the days setting
+//! [`AoCProblem::GENERATES`](crate::problem::AoCProblem::GENERATES), run by
+//! the `stress` subcommand to find panics and measure how solvers scale.
+
+use std::ops::RangeInclusive;
+
+/// Small deterministic random generator (SplitMix64), so that a generated
+/// input can be reproduced from its seed.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value below `n`, which must not be 0.
+    pub fn below(&mut self, n: u64) -> u64 {
+        // rejects the values of the last incomplete block, which would be biased
+        let limit = u64::MAX - u64::MAX % n;
+        loop {
+            let value = self.next_u64();
+            if value < limit {
+                return value % n;
+            }
+        }
+    }
+
+    /// Uniform value in `range`.
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+        let width = end.abs_diff(start).wrapping_add(1);
+        let offset = if width == 0 { self.next_u64() } else { self.below(width) };
+        start.wrapping_add(offset as i64)
+    }
+
+    /// True with the given probability.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+
+    /// Random item of `items`, which must not be empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn deterministic() {
+        let (mut a, mut b) = (Rng::new(42), Rng::new(42));
+        let values: Vec<u64> = (0..5).map(|_| a.next_u64()).collect();
+        assert_eq!(values, (0..5).map(|_| b.next_u64()).collect::<Vec<_>>());
+        assert_ne!(Rng::new(43).next_u64(), values[0]);
+    }
+
+    #[test]
+    fn ranges() {
+        let mut rng = Rng::new(7);
+        for _ in 0..1000 {
+            assert!((-3..=3).contains(&rng.range(-3..=3)));
+            assert!(rng.below(10) < 10);
+        }
+        assert_eq!(rng.range(5..=5), 5);
+        rng.range(i64::MIN..=i64::MAX);
+        assert!(!rng.chance(0.0) && rng.chance(1.0));
+
+        let mut items = [1, 2, 3, 4, 5];
+        rng.shuffle(&mut items);
+        items.sort();
+        assert_eq!(items, [1, 2, 3, 4, 5]);
+    }
+}
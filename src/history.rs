@@ -0,0 +1,188 @@
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::{fs, io};
+
+use anyhow::{anyhow, bail, Error, Result};
+
+use crate::test_case::day_dir;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::Wrong => "wrong",
+        })
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "correct" => Verdict::Correct,
+            "too_high" => Verdict::TooHigh,
+            "too_low" => Verdict::TooLow,
+            "wrong" => Verdict::Wrong,
+            _ => bail!("invalid verdict {}", s),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    part: u32,
+    verdict: Verdict,
+    answer: String,
+}
+
+/// Answers already submitted for a day, stored one per line as
+/// `part<TAB>verdict<TAB>answer` in `input/YYYY/NN/history.log`.
+#[derive(Debug, Default)]
+pub struct History {
+    path: PathBuf,
+    entries: Vec<Entry>,
+}
+
+impl History {
+    pub fn path(root: &Path, year: u32, day: u32) -> PathBuf {
+        day_dir(root, year, day).join("history.log")
+    }
+
+    pub fn load(root: &Path, year: u32, day: u32) -> Result<Self> {
+        let path = Self::path(root, year, day);
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut entries = Vec::new();
+        for line in content.lines().filter(|l| !l.is_empty()) {
+            let mut fields = line.splitn(3, '\t');
+            let (Some(part), Some(verdict), Some(answer)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                bail!("invalid history line: {}", line);
+            };
+            entries.push(Entry {
+                part: part.parse()?,
+                verdict: verdict.parse()?,
+                answer: answer.into(),
+            });
+        }
+
+        Ok(Self { path, entries })
+    }
+
+    pub fn record(&mut self, part: u32, answer: &str, verdict: Verdict) -> Result<()> {
+        self.entries.push(Entry {
+            part,
+            verdict,
+            answer: answer.into(),
+        });
+
+        let content = self
+            .entries
+            .iter()
+            .map(|e| format!("{}\t{}\t{}\n", e.part, e.verdict, e.answer))
+            .collect::<String>();
+        fs::write(&self.path, content).map_err(|e| anyhow!("cannot write history: {}", e))
+    }
+
+    /// Returns why `answer` can't be the right one for `part` according to
+    /// previous submissions, if any.
+    pub fn check(&self, part: u32, answer: &str) -> Option<String> {
+        let answer = answer.trim();
+        let value = answer.parse::<i128>().ok();
+
+        for entry in self.entries.iter().filter(|e| e.part == part) {
+            if entry.answer == answer {
+                if entry.verdict != Verdict::Correct {
+                    return Some(format!("{} was already submitted as {}", answer, entry.verdict));
+                }
+                continue;
+            }
+
+            let bound = entry.answer.parse::<i128>().ok();
+            match (entry.verdict, value, bound) {
+                (Verdict::Correct, _, _) => {
+                    return Some(format!("accepted answer was {}", entry.answer));
+                }
+                (Verdict::TooHigh, Some(v), Some(b)) if v >= b => {
+                    return Some(format!("{} is too high, {} already was", answer, entry.answer));
+                }
+                (Verdict::TooLow, Some(v), Some(b)) if v <= b => {
+                    return Some(format!("{} is too low, {} already was", answer, entry.answer));
+                }
+                _ => {}
+            }
+        }
+
+        None
+    }
+
+    pub fn solved(&self, part: u32) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|e| e.part == part && e.verdict == Verdict::Correct)
+            .map(|e| e.answer.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn history(entries: &[(u32, Verdict, &str)]) -> History {
+        History {
+            path: PathBuf::new(),
+            entries: entries
+                .iter()
+                .map(|&(part, verdict, answer)| Entry {
+                    part,
+                    verdict,
+                    answer: answer.into(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn known_wrong_answer() {
+        let history = history(&[(1, Verdict::Wrong, "abc")]);
+        assert!(history.check(1, "abc").is_some());
+        assert!(history.check(1, "abd").is_none());
+        assert!(history.check(2, "abc").is_none());
+    }
+
+    #[test]
+    fn bounds() {
+        let history = history(&[(1, Verdict::TooHigh, "100"), (1, Verdict::TooLow, "10")]);
+        assert!(history.check(1, "100").is_some());
+        assert!(history.check(1, "150").is_some());
+        assert!(history.check(1, "10").is_some());
+        assert!(history.check(1, "5").is_some());
+        assert!(history.check(1, "50").is_none());
+    }
+
+    #[test]
+    fn correct_answer() {
+        let history = history(&[(2, Verdict::TooLow, "10"), (2, Verdict::Correct, "42")]);
+        assert!(history.check(2, "42").is_none());
+        assert!(history.check(2, "43").is_some());
+        assert_eq!(history.solved(2), Some("42"));
+        assert_eq!(history.solved(1), None);
+    }
+}
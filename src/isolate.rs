@@ -0,0 +1,72 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Outcome<R> {
+    Done(R),
+    Panic(String),
+    Timeout,
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".into()
+    }
+}
+
+/// Runs `f` on its own thread, catching panics and giving up after `timeout`.
+///
+/// A thread that times out can't be killed: it is left running in the
+/// background until the process exits.
+pub fn run_isolated<R, F>(timeout: Option<Duration>, f: F) -> Outcome<R>
+where
+    R: Send + 'static,
+    F: FnOnce() -> R + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let _ = sender.send(panic::catch_unwind(AssertUnwindSafe(f)));
+    });
+
+    let result = match timeout {
+        Some(timeout) => receiver.recv_timeout(timeout),
+        None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+    };
+    match result {
+        Ok(Ok(result)) => Outcome::Done(result),
+        Ok(Err(payload)) => Outcome::Panic(panic_message(payload)),
+        Err(RecvTimeoutError::Timeout) => Outcome::Timeout,
+        Err(RecvTimeoutError::Disconnected) => Outcome::Panic("thread exited".into()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn done() {
+        assert_eq!(run_isolated(None, || 42), Outcome::Done(42));
+    }
+
+    #[test]
+    fn panic() {
+        let outcome: Outcome<()> = run_isolated(None, || panic!("invalid {}", "input"));
+        assert_eq!(outcome, Outcome::Panic("invalid input".into()));
+    }
+
+    #[test]
+    fn timeout() {
+        let outcome = run_isolated(Some(Duration::from_millis(10)), || {
+            thread::sleep(Duration::from_secs(1));
+        });
+        assert_eq!(outcome, Outcome::Timeout);
+    }
+}
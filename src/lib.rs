@@ -0,0 +1,19 @@
+//! Framework to solve Advent of Code problems: solutions implement
+//! [`problem::AoCProblem`] and are run by [`runner::run_on_input`] against the
+//! test cases found in `input/YYYY/NN` (or any explicit input).
+
+pub mod args;
+pub mod generate;
+pub mod history;
+pub mod isolate;
+pub mod memory;
+pub mod problem;
+pub mod runner;
+pub mod scaffold;
+pub mod stress;
+pub mod submit;
+pub mod test_case;
+pub mod trace;
+pub mod utils;
+pub mod visualize;
+pub mod watch;
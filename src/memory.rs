@@ -0,0 +1,143 @@
+//! Counting allocator, installed as the global allocator by the
+//! `memory-profiling` feature, to report the memory used by a solver.
+//!
+//! Counters are kept per thread, so measuring a part isn't affected by other
+//! cases running in parallel.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt::Display;
+
+/// Whether allocations are counted, that is if [`measure`] reports anything.
+pub const ENABLED: bool = cfg!(feature = "memory-profiling");
+
+thread_local! {
+    static CURRENT: Cell<isize> = const { Cell::new(0) };
+    static PEAK: Cell<isize> = const { Cell::new(0) };
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn record_alloc(size: usize) {
+    // try_with, since the allocator is also used while thread locals are destroyed
+    let _ = CURRENT.try_with(|current| {
+        current.set(current.get() + size as isize);
+        let _ = PEAK.try_with(|peak| peak.set(peak.get().max(current.get())));
+    });
+    let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+}
+
+fn record_dealloc(size: usize) {
+    let _ = CURRENT.try_with(|current| current.set(current.get() - size as isize));
+}
+
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[cfg(feature = "memory-profiling")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// highest amount of memory in use at the same time, in bytes
+    pub peak: usize,
+    pub allocations: usize,
+}
+
+impl Display for MemoryStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+        let mut peak = self.peak as f64;
+        let mut unit = 0;
+        while peak >= 1024.0 && unit < UNITS.len() - 1 {
+            peak /= 1024.0;
+            unit += 1;
+        }
+
+        if unit == 0 {
+            write!(f, "{}B peak, {} allocs", self.peak, self.allocations)
+        } else {
+            write!(f, "{:.1}{} peak, {} allocs", peak, UNITS[unit], self.allocations)
+        }
+    }
+}
+
+/// Runs `f` returning the memory it allocated on the current thread.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, MemoryStats) {
+    let start = CURRENT.with(Cell::get);
+    let start_allocations = ALLOCATIONS.with(Cell::get);
+    PEAK.with(|peak| peak.set(start));
+
+    let result = f();
+
+    let stats = MemoryStats {
+        peak: (PEAK.with(Cell::get) - start).max(0) as usize,
+        allocations: ALLOCATIONS.with(Cell::get) - start_allocations,
+    };
+    (result, stats)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn format_stats() {
+        let stats = MemoryStats {
+            peak: 512,
+            allocations: 3,
+        };
+        assert_eq!(stats.to_string(), "512B peak, 3 allocs");
+
+        let stats = MemoryStats {
+            peak: 3 * 1024 * 1024 / 2,
+            allocations: 10,
+        };
+        assert_eq!(stats.to_string(), "1.5MiB peak, 10 allocs");
+    }
+
+    #[cfg(feature = "memory-profiling")]
+    #[test]
+    fn measure_allocations() {
+        let (_, stats) = measure(|| {
+            let a = vec![0u8; 1000];
+            let b = vec![0u8; 2000];
+            drop(a);
+            drop(b);
+        });
+        assert_eq!(stats.allocations, 2);
+        assert_eq!(stats.peak, 3000);
+    }
+}
@@ -1,8 +1,142 @@
-use std::{fmt::Debug, str::FromStr};
+use std::fmt::{self, Debug, Display};
+use std::str::FromStr;
 
 use anyhow::Result;
 
+use crate::generate::Rng;
+use crate::utils::bigint::BigInt;
+use crate::visualize::Visualize;
+
+/// Lines of `Debug` output kept by the default [`AoCProblem::summary`].
+const SUMMARY_LINES: usize = 40;
+
 pub trait AoCProblem: Debug + FromStr {
-    fn solve_part1(&self) -> Result<String>;
-    fn solve_part2(&self) -> Result<String>;
+    /// Whether the runner normalizes the input before parsing it, see
+    /// [`crate::test_case::normalize`]. Days where whitespace matters opt out.
+    const NORMALIZE_INPUT: bool = true;
+
+    /// Whether the day implements [`AoCProblem::generate_input`], so that
+    /// `stress` can run it.
+    const GENERATES: bool = false;
+
+    fn solve_part1(&self) -> Result<Answer>;
+    fn solve_part2(&self) -> Result<Answer>;
+
+    /// Readable description of the parsed input for `--dump-input`, such as
+    /// its sizes and a compact rendering. Defaults to the start of the
+    /// `Debug` output.
+    fn summary(&self) -> String {
+        truncated_debug(self, SUMMARY_LINES)
+    }
+
+    /// Random valid input with about `size` elements (lines, hands, rows...),
+    /// always the same for the same state of `rng`. Only called on the days
+    /// setting [`AoCProblem::GENERATES`].
+    fn generate_input(_size: usize, _rng: &mut Rng) -> String {
+        unimplemented!("this day doesn't generate inputs")
+    }
+
+    /// Days implementing [`Visualize`] return themselves, so that the runner
+    /// can render them with `--visualize`.
+    fn visualization(&self) -> Option<&dyn Visualize> {
+        None
+    }
+}
+
+/// Answer of a part, compared with the expected one and stored as its text.
+/// Integers of any primitive type convert to it without loss, larger ones
+/// are computed as a [`BigInt`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i128),
+    Big(BigInt),
+    Text(String),
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Answer::Int(n.into())
+            }
+        })*
+    };
+}
+
+answer_from_int!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Int(n as i128)
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        match i128::try_from(n) {
+            Ok(n) => Answer::Int(n),
+            Err(_) => Answer::Big(n.into()),
+        }
+    }
+}
+
+impl From<BigInt> for Answer {
+    fn from(n: BigInt) -> Self {
+        Answer::Big(n)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::Big(n) => write!(f, "{}", n),
+            Answer::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+/// Pretty `Debug` output of `value`, cut after `max_lines` lines.
+pub fn truncated_debug(value: &impl Debug, max_lines: usize) -> String {
+    let debug = format!("{:#?}", value);
+    let lines: Vec<&str> = debug.lines().collect();
+    if lines.len() <= max_lines {
+        return debug;
+    }
+
+    let mut summary = lines[..max_lines].join("\n");
+    summary.push_str(&format!("\n... {} more lines", lines.len() - max_lines));
+    summary
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn truncated() {
+        assert_eq!(truncated_debug(&[1, 2], 5), "[\n    1,\n    2,\n]");
+        assert_eq!(truncated_debug(&[1, 2], 2), "[\n    1,\n... 2 more lines");
+    }
+
+    #[test]
+    fn answers() {
+        assert_eq!(Answer::from(u64::MAX).to_string(), "18446744073709551615");
+        assert_eq!(Answer::from(-3i32).to_string(), "-3");
+        assert_eq!(Answer::from(u128::MAX).to_string(), u128::MAX.to_string());
+        assert_eq!(Answer::from(BigInt::from(10).pow(40)).to_string(), format!("1{}", "0".repeat(40)));
+        assert_eq!(Answer::from("abc").to_string(), "abc");
+    }
 }
@@ -0,0 +1,535 @@
+use colored::{ColoredString, Colorize};
+
+use std::fmt::Display;
+use std::fs;
+use std::io::{self, ErrorKind, Write};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Instant;
+
+use anyhow::{anyhow, bail, Error, Result};
+
+use crate::args::{DumpMode, RunArgs};
+use crate::generate::Rng;
+use crate::history::History;
+use crate::isolate::{run_isolated, Outcome};
+use crate::memory::{self, MemoryStats};
+use crate::problem::AoCProblem;
+use crate::trace::{self, Level, TraceLog};
+use crate::test_case::{load_input, load_test_cases, normalize, TestCase};
+use crate::visualize::{render_terminal, save};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Correct,
+    Wrong,
+    Unknown,
+    Error,
+    Panic,
+    Timeout,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Status::Correct => "CORRECT",
+            Status::Wrong => "WRONG",
+            Status::Unknown => "UNKNOWN",
+            Status::Error => "ERROR",
+            Status::Panic => "PANIC",
+            Status::Timeout => "TIMEOUT",
+        })
+    }
+}
+
+impl FromStr for Status {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "CORRECT" => Status::Correct,
+            "WRONG" => Status::Wrong,
+            "UNKNOWN" => Status::Unknown,
+            "ERROR" => Status::Error,
+            "PANIC" => Status::Panic,
+            "TIMEOUT" => Status::Timeout,
+            _ => bail!("invalid status {}", s),
+        })
+    }
+}
+
+/// Status of each part of a case, `None` for the parts that weren't run.
+pub type Statuses = [Option<Status>; 2];
+
+/// A single case of a day to run.
+pub struct Task {
+    pub day: u32,
+    pub case: TestCase,
+}
+
+/// Loads the cases selected by `args`, in day and case order.
+pub fn load_tasks(args: &RunArgs) -> Result<Vec<Task>> {
+    let days = if args.all {
+        (1..=25).collect()
+    } else {
+        vec![args.day.ok_or(anyhow!("missing day"))?]
+    };
+
+    let mut tasks = Vec::new();
+    for day in days {
+        let cases = match &args.input {
+            Some(path) => load_input(path)?,
+            None => match load_test_cases(&args.input_dir, args.year, day) {
+                Ok(cases) => cases,
+                Err(e) if args.all && e.kind() == ErrorKind::NotFound => continue,
+                Err(e) => return Err(e.into()),
+            },
+        };
+
+        for case in cases {
+            if let Some(filter) = &args.input_filter {
+                if !case.name().contains(filter) {
+                    continue
+                }
+            }
+            tasks.push(Task { day, case });
+        }
+    }
+
+    Ok(tasks)
+}
+
+/// Runs every task, `jobs` at a time, printing the reports in the order of
+/// `tasks` as soon as all the previous ones are complete.
+pub fn run_tasks<R, F>(year: u32, tasks: &[Task], jobs: usize, run: F) -> io::Result<Vec<R>>
+where
+    R: Send,
+    F: Fn(&Task, &mut dyn Write) -> io::Result<R> + Sync,
+{
+    let run_task = |task: &Task, out: &mut dyn Write| -> io::Result<R> {
+        let start_time = Instant::now();
+        let result = run(task, out)?;
+        writeln!(
+            out,
+            "  {} case done ({}ms)",
+            "->".blue().bold(),
+            start_time.elapsed().as_millis()
+        )?;
+        Ok(result)
+    };
+
+    let mut stdout = io::stdout();
+    let mut current_day = None;
+    let mut print_day = |day: u32, out: &mut dyn Write| -> io::Result<()> {
+        if current_day != Some(day) {
+            current_day = Some(day);
+            writeln!(out, "{} solving day {} of {}", "==>".green().bold(), day, year)?;
+        }
+        Ok(())
+    };
+
+    let mut results = Vec::with_capacity(tasks.len());
+    if jobs <= 1 {
+        for task in tasks {
+            print_day(task.day, &mut stdout)?;
+            results.push(run_task(task, &mut stdout)?);
+        }
+        return Ok(results);
+    }
+
+    let next_task = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(tasks.len()) {
+            let sender = sender.clone();
+            let (next_task, run_task) = (&next_task, &run_task);
+            scope.spawn(move || loop {
+                let i = next_task.fetch_add(1, Ordering::Relaxed);
+                let Some(task) = tasks.get(i) else { break };
+                let mut report = Vec::new();
+                let result = run_task(task, &mut report);
+                if sender.send((i, report, result)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        let mut reports: Vec<Option<(Vec<u8>, io::Result<R>)>> =
+            (0..tasks.len()).map(|_| None).collect();
+        let mut next_report = 0;
+        for (i, report, result) in receiver {
+            reports[i] = Some((report, result));
+            while let Some((report, result)) = reports.get_mut(next_report).and_then(Option::take) {
+                print_day(tasks[next_report].day, &mut stdout)?;
+                stdout.write_all(&report)?;
+                results.push(result?);
+                next_report += 1;
+            }
+        }
+
+        Ok(results)
+    })
+}
+
+/// What the runner needs of a day, built from its type by [`solver`] in the
+/// day dispatch of `main.rs`.
+#[derive(Clone, Copy)]
+pub struct Solver {
+    pub run: fn(&RunArgs, u32, u32, &TestCase, &mut dyn Write) -> io::Result<Statuses>,
+    /// see [`AoCProblem::GENERATES`]
+    pub generates: bool,
+}
+
+pub fn solver<T>() -> Solver
+where
+    T: AoCProblem + Send + Sync + 'static,
+    <T as FromStr>::Err: Display,
+{
+    Solver {
+        run: run_on_input::<T>,
+        generates: T::GENERATES,
+    }
+}
+
+pub fn run_on_input<T>(
+    args: &RunArgs,
+    year: u32,
+    day: u32,
+    case: &TestCase,
+    out: &mut dyn Write,
+) -> io::Result<Statuses>
+where
+    T: AoCProblem + Send + Sync + 'static,
+    <T as FromStr>::Err: Display,
+{
+    writeln!(
+        out,
+        "{} Running on case: {}",
+        "==>".green().bold(),
+        case.name()
+    )?;
+
+    let timeout = args.timeout();
+    let mut input = match case.generated_input() {
+        Some(_) if !T::GENERATES => {
+            writeln!(out, "{} day {} can't generate inputs", "==>".red().bold(), day)?;
+            return Ok(failed_statuses(args, Status::Error));
+        }
+        Some((size, seed)) => {
+            match run_isolated(timeout, move || T::generate_input(size, &mut Rng::new(seed))) {
+                Outcome::Done(input) => input,
+                Outcome::Panic(message) => {
+                    writeln!(out, "{} generation PANIC: {}", "==>".red().bold(), message)?;
+                    return Ok(failed_statuses(args, Status::Panic));
+                }
+                Outcome::Timeout => {
+                    writeln!(out, "{} generation TIMEOUT", "==>".red().bold())?;
+                    return Ok(failed_statuses(args, Status::Timeout));
+                }
+            }
+        }
+        None => case.input().to_string(),
+    };
+
+    if T::NORMALIZE_INPUT {
+        let (normalized, changes) = normalize(&input);
+        if !changes.is_empty() {
+            let message = format!("input normalized: {}", changes.join(", "));
+            writeln!(out, "  {} {}", "->".yellow().bold(), message.yellow())?;
+        }
+        input = normalized;
+    }
+
+    let verbosity = args.verbosity(day);
+    let parsing_time: Instant = Instant::now();
+    let problem = run_isolated(timeout, move || {
+        memory::measure(|| trace::collect(verbosity, || input.parse::<T>().map_err(|e| e.to_string())))
+    });
+    match problem {
+        Outcome::Done(((Ok(problem), log), memory_stats)) => {
+            writeln!(
+                out,
+                "  {} parsing OK ({}ms{})",
+                "->".blue().bold(),
+                parsing_time.elapsed().as_millis(),
+                format_memory(memory_stats)
+            )?;
+            print_trace(&log, out)?;
+
+            if let Some(mode) = args.dump_input {
+                let dump = match mode {
+                    DumpMode::Summary => problem.summary(),
+                    DumpMode::Full => format!("{:#?}", problem),
+                };
+                dump_input(args, &dump, &case_name(year, day, case), out)?;
+            }
+
+            let history = if case.is_puzzle_input() {
+                History::load(&args.input_dir, year, day).ok()
+            } else {
+                None
+            };
+
+            if args.visualize.is_some() && problem.visualization().is_none() {
+                let message = format!("day {} can't be visualized", day);
+                writeln!(out, "  {} {}", "->".yellow().bold(), message.yellow())?;
+            }
+
+            let problem = Arc::new(problem);
+            let mut solutions = [None, None];
+            let mut statuses = [None, None];
+            for (i, part) in [1, 2].into_iter().enumerate() {
+                if args.runs_part(part) {
+                    let (solution, status) =
+                        solve_part(&problem, case, history.as_ref(), args, day, part, out)?;
+                    solutions[i] = Some(solution);
+                    statuses[i] = Some(status);
+                    visualize_part(args, &problem, year, day, case, part, out)?;
+                }
+            }
+            let [part_1, part_2] = solutions;
+
+            if args.store_output {
+                // only store when every part that was run succeeded
+                if let (Some(None), _) | (_, Some(None)) = (&part_1, &part_2) {
+                    return Ok(statuses);
+                }
+
+                writeln!(out, "  {} writing result to ans file", "->".blue().bold())?;
+                match case.write_output(part_1.flatten().as_deref(), part_2.flatten().as_deref()) {
+                    Ok(_) => {}
+                    Err(_) => writeln!(out, "{} ", "==> write failed".red().bold())?,
+                }
+            }
+
+            Ok(statuses)
+        }
+        Outcome::Done(((Err(error), log), _)) => {
+            writeln!(out, "{} {}", "==>".red().bold(), error)?;
+            print_trace(&log, out)?;
+            Ok(failed_statuses(args, Status::Error))
+        }
+        Outcome::Panic(message) => {
+            writeln!(out, "{} parsing PANIC: {}", "==>".red().bold(), message)?;
+            Ok(failed_statuses(args, Status::Panic))
+        }
+        Outcome::Timeout => {
+            writeln!(out, "{} parsing TIMEOUT", "==>".red().bold())?;
+            Ok(failed_statuses(args, Status::Timeout))
+        }
+    }
+}
+
+/// Name of the files written for a case, like `2023-day10-input`.
+fn case_name(year: u32, day: u32, case: &TestCase) -> String {
+    let name = case.name();
+    let stem = Path::new(&name).file_stem().and_then(|s| s.to_str()).unwrap_or("case");
+    format!("{}-day{:02}-{}", year, day, stem)
+}
+
+/// Prints the dump of the parsed input, or saves it in the `--dump-dir`.
+fn dump_input(args: &RunArgs, dump: &str, name: &str, out: &mut dyn Write) -> io::Result<()> {
+    let dump = dump.trim_end();
+    let Some(dir) = &args.dump_dir else {
+        return writeln!(out, "PARSED INPUT:\n{}", dump);
+    };
+
+    let path = dir.join(format!("{}.txt", name));
+    match fs::create_dir_all(dir).and_then(|_| fs::write(&path, format!("{}\n", dump))) {
+        Ok(()) => writeln!(out, "  {} input dumped to {}", "->".blue().bold(), path.display()),
+        Err(e) => {
+            let message = format!("input not dumped: {}", e);
+            writeln!(out, "  {} {}", "->".yellow().bold(), message.yellow())
+        }
+    }
+}
+
+/// Renders the frames of a part on `out`, or saves them in the `--visualize`
+/// directory, for the days that implement it.
+fn visualize_part<T>(
+    args: &RunArgs,
+    problem: &Arc<T>,
+    year: u32,
+    day: u32,
+    case: &TestCase,
+    part: u32,
+    out: &mut dyn Write,
+) -> io::Result<()>
+where
+    T: AoCProblem + Send + Sync + 'static,
+{
+    let Some(target) = &args.visualize else {
+        return Ok(());
+    };
+    if problem.visualization().is_none() {
+        return Ok(());
+    }
+
+    let problem = Arc::clone(problem);
+    let outcome = run_isolated(args.timeout(), move || {
+        problem.visualization().map(|v| v.frames(part)).unwrap_or_default()
+    });
+    let frames = match outcome {
+        Outcome::Done(frames) => frames,
+        Outcome::Panic(message) => {
+            let message = format!("part {} visualization PANIC: {}", part, message);
+            return writeln!(out, "  {} {}", "->".yellow().bold(), message.yellow());
+        }
+        Outcome::Timeout => {
+            let message = format!("part {} visualization TIMEOUT", part);
+            return writeln!(out, "  {} {}", "->".yellow().bold(), message.yellow());
+        }
+    };
+
+    if target == Path::new("-") {
+        return render_terminal(&frames, out);
+    }
+
+    let name = format!("{}-part{}", case_name(year, day, case), part);
+    match save(target, &name, &frames, args.frame_format) {
+        Ok(paths) => writeln!(
+            out,
+            "  {} part {} visualization: {} frames in {} file(s) in {}",
+            "->".blue().bold(),
+            part,
+            frames.len(),
+            paths.len(),
+            target.display()
+        ),
+        Err(e) => {
+            let message = format!("part {} visualization not saved: {}", part, e);
+            writeln!(out, "  {} {}", "->".yellow().bold(), message.yellow())
+        }
+    }
+}
+
+/// Prints the messages and counters recorded by a solver.
+fn print_trace(log: &TraceLog, out: &mut dyn Write) -> io::Result<()> {
+    for (level, message) in &log.events {
+        let level = match level {
+            Level::Debug => "debug".cyan(),
+            Level::Trace => "trace".dimmed(),
+        };
+        writeln!(out, "     {} {}", level, message)?;
+    }
+    if log.dropped > 0 {
+        writeln!(out, "     {} {} more messages", "...".dimmed(), log.dropped)?;
+    }
+    for (name, value) in &log.counters {
+        writeln!(out, "     {} {} = {}", "count".magenta(), name, value)?;
+    }
+    Ok(())
+}
+
+/// Statuses of the parts that couldn't run because parsing failed.
+fn failed_statuses(args: &RunArgs, status: Status) -> Statuses {
+    [1, 2].map(|part| args.runs_part(part).then_some(status))
+}
+
+/// Memory stats to show next to a timing, when they are collected.
+fn format_memory(stats: MemoryStats) -> String {
+    if memory::ENABLED {
+        format!(", {}", stats)
+    } else {
+        String::new()
+    }
+}
+
+fn validate(ans: &String, expected: &Option<String>) -> (Status, ColoredString) {
+    if let Some(expected) = expected {
+        if expected.trim() == ans {
+            (Status::Correct, "CORRECT".green())
+        } else {
+            (Status::Wrong, " WRONG ".red())
+        }
+    } else {
+        (Status::Unknown, "UNKNOWN".yellow())
+    }
+}
+
+fn solve_part<T>(
+    problem: &Arc<T>,
+    case: &TestCase,
+    history: Option<&History>,
+    args: &RunArgs,
+    day: u32,
+    part: u32,
+    out: &mut dyn Write,
+) -> io::Result<(Option<String>, Status)>
+where
+    T: AoCProblem + Send + Sync + 'static,
+{
+    let start_time = Instant::now();
+    let problem = Arc::clone(problem);
+    let verbosity = args.verbosity(day);
+    let outcome = run_isolated(args.timeout(), move || {
+        memory::measure(|| {
+            trace::collect(verbosity, || match part {
+                1 => problem.solve_part1(),
+                2 => problem.solve_part2(),
+                _ => panic!("invalid part"),
+            })
+        })
+    });
+    let elapsed_time = start_time.elapsed().as_micros();
+
+    let ((answer, log), memory_stats) = match outcome {
+        Outcome::Done(result) => result,
+        Outcome::Panic(message) => {
+            writeln!(
+                out,
+                "  {} part {} = {} ({})",
+                "->".blue().bold(),
+                part,
+                "PANIC".red(),
+                message
+            )?;
+            return Ok((None, Status::Panic));
+        }
+        Outcome::Timeout => {
+            writeln!(
+                out,
+                "  {} part {} = {} ({}ms)",
+                "->".blue().bold(),
+                part,
+                "TIMEOUT".red(),
+                elapsed_time / 1000
+            )?;
+            return Ok((None, Status::Timeout));
+        }
+    };
+
+    let solution = answer.map(|answer| answer.to_string());
+    let solution_string = solution
+        .as_ref()
+        .map(String::clone)
+        .unwrap_or("ERROR".red().to_string());
+    let (mut status, validation) = validate(&solution_string, &case.output(part).map(String::from));
+    if solution.is_err() {
+        status = Status::Error;
+    }
+    writeln!(
+        out,
+        "  {} part {} = {} [{}] ({}μs{})",
+        "->".blue().bold(),
+        part,
+        solution_string,
+        validation,
+        elapsed_time,
+        format_memory(memory_stats)
+    )?;
+
+    print_trace(&log, out)?;
+
+    if let (Some(history), Ok(solution)) = (history, &solution) {
+        if let Some(reason) = history.check(part, solution) {
+            writeln!(out, "  {} {}", "->".yellow().bold(), reason.yellow())?;
+        }
+    }
+
+    Ok((solution.ok(), status))
+}
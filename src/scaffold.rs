@@ -0,0 +1,170 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Result};
+use colored::Colorize;
+
+use crate::args::NewArgs;
+use crate::test_case::day_dir;
+
+const DAY_TEMPLATE: &str = include_str!("templates/day.rs.tmpl");
+const DAY_PEST_TEMPLATE: &str = include_str!("templates/day_pest.rs.tmpl");
+const GRAMMAR_TEMPLATE: &str = include_str!("templates/day.pest.tmpl");
+
+fn render(template: &str, year: u32, day: u32) -> String {
+    template
+        .replace("{year}", &year.to_string())
+        .replace("{day:02}", &format!("{:02}", day))
+        .replace("{day}", &day.to_string())
+}
+
+/// Writes `content` to `path`, unless the file already exists with something
+/// other than the unmodified stub.
+fn write_source(path: &Path, content: &str, stub: Option<&str>, force: bool) -> Result<()> {
+    if let Ok(existing) = fs::read_to_string(path) {
+        if existing == content {
+            return Ok(());
+        }
+        if !force && stub != Some(existing.as_str()) {
+            bail!("{} already exists, use --force to overwrite", path.display());
+        }
+    }
+
+    fs::write(path, content)?;
+    println!("  {} created {}", "->".blue().bold(), path.display());
+
+    Ok(())
+}
+
+/// Inserts `line` in the block of lines starting with `prefix`, keeping it
+/// sorted, if not already present. Without such a block the line is appended.
+fn register(source: &str, prefix: &str, line: &str) -> String {
+    if source.lines().any(|l| l == line) {
+        return source.into();
+    }
+
+    let mut lines: Vec<&str> = source.lines().collect();
+    let position = lines
+        .iter()
+        .position(|l| l.starts_with(prefix) && *l > line)
+        .or_else(|| lines.iter().rposition(|l| l.starts_with(prefix)).map(|i| i + 1))
+        .unwrap_or(lines.len());
+    lines.insert(position, line);
+
+    lines.join("\n") + "\n"
+}
+
+/// Parses the `(year, day)` pattern of a dispatch arm of the runner.
+fn dispatch_arm(line: &str) -> Option<(u32, u32)> {
+    let (pattern, _) = line.trim_start().split_once(" => Some(solver::")?;
+    let (year, day) = pattern.strip_prefix('(')?.strip_suffix(')')?.split_once(", ")?;
+    Some((year.parse().ok()?, day.parse().ok()?))
+}
+
+fn register_in_runner(source: &str, year: u32, day: u32) -> Result<String> {
+    let source = register(source, "mod y", &format!("mod y{};", year));
+
+    let arm = format!(
+        "        ({}, {}) => Some(solver::<y{}::day{:02}::AoCDay{}>()),",
+        year, day, year, day, day
+    );
+    if source.lines().any(|l| l == arm) {
+        return Ok(source);
+    }
+
+    let mut lines: Vec<&str> = source.lines().collect();
+    let position = lines
+        .iter()
+        .position(|l| dispatch_arm(l).is_some_and(|arm| arm > (year, day)))
+        .or_else(|| lines.iter().position(|l| l.trim() == "_ => None,"))
+        .ok_or(anyhow!("cannot find the day dispatch in the runner"))?;
+    lines.insert(position, &arm);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Creates a new day in the crate found in the current directory, which is
+/// expected to dispatch days from `src/main.rs` like this repository does.
+pub fn new_day(args: &NewArgs) -> Result<()> {
+    let (year, day) = (args.year, args.day);
+    println!("{} creating day {} of {}", "==>".green().bold(), day, year);
+
+    let year_dir = PathBuf::from(format!("src/y{}", year));
+    fs::create_dir_all(&year_dir)?;
+
+    let source_path = year_dir.join(format!("day{:02}.rs", day));
+    let stub = render(DAY_TEMPLATE, year, day);
+    if args.pest {
+        let source = render(DAY_PEST_TEMPLATE, year, day);
+        write_source(&source_path, &source, Some(&stub), args.force)?;
+        let grammar_path = source_path.with_extension("pest");
+        write_source(&grammar_path, &render(GRAMMAR_TEMPLATE, year, day), None, args.force)?;
+    } else {
+        write_source(&source_path, &stub, None, args.force)?;
+    }
+
+    let mod_path = year_dir.join("mod.rs");
+    let mod_source = fs::read_to_string(&mod_path).unwrap_or_default();
+    fs::write(
+        &mod_path,
+        register(&mod_source, "pub mod day", &format!("pub mod day{:02};", day)),
+    )?;
+
+    let main_path = Path::new("src/main.rs");
+    let main_source = fs::read_to_string(main_path)?;
+    fs::write(main_path, register_in_runner(&main_source, year, day)?)?;
+
+    let input_dir = day_dir(&args.input_dir, year, day);
+    fs::create_dir_all(&input_dir)?;
+    let example_path = input_dir.join("example.txt");
+    if !example_path.exists() {
+        fs::write(&example_path, "")?;
+        println!("  {} created {}", "->".blue().bold(), example_path.display());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn render_template() {
+        let source = render(DAY_PEST_TEMPLATE, 2023, 7);
+        assert!(source.contains("pub struct AoCDay7 {}"));
+        assert!(source.contains("#[grammar = \"src/y2023/day07.pest\"]"));
+        assert!(source.contains("struct Day07Parser;"));
+    }
+
+    #[test]
+    fn register_sorted() {
+        let source = "pub mod day01;\npub mod day03;\n";
+        assert_eq!(
+            register(source, "pub mod day", "pub mod day02;"),
+            "pub mod day01;\npub mod day02;\npub mod day03;\n"
+        );
+        assert_eq!(
+            register(source, "pub mod day", "pub mod day04;"),
+            "pub mod day01;\npub mod day03;\npub mod day04;\n"
+        );
+        assert_eq!(register(source, "pub mod day", "pub mod day03;"), source);
+        assert_eq!(register("", "pub mod day", "pub mod day01;"), "pub mod day01;\n");
+    }
+
+    #[test]
+    fn register_runner() {
+        let source = "mod utils;\nmod y2023;\n\nfn day_solver() {\n    match (year, day) {\n        (2023, 1) => Some(solver::<y2023::day01::AoCDay1>()),\n        _ => None,\n    }\n}\n";
+        let result = register_in_runner(source, 2023, 2).unwrap();
+        assert!(result.contains(
+            "        (2023, 1) => Some(solver::<y2023::day01::AoCDay1>()),\n        (2023, 2) => Some(solver::<y2023::day02::AoCDay2>()),\n        _ => None,"
+        ));
+        assert_eq!(register_in_runner(&result, 2023, 2).unwrap(), result);
+
+        let result = register_in_runner(&result, 2024, 1).unwrap();
+        assert!(result.contains("mod y2023;\nmod y2024;\n"));
+        assert!(result.contains(
+            "(2023, 2) => Some(solver::<y2023::day02::AoCDay2>()),\n        (2024, 1) => Some(solver::<y2024::day01::AoCDay1>()),\n"
+        ));
+    }
+}
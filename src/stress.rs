@@ -0,0 +1,111 @@
+use std::io;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Result};
+use colored::Colorize;
+
+use crate::args::{RunArgs, StressArgs};
+use crate::runner::{Status, Statuses, Task};
+use crate::test_case::TestCase;
+
+/// Results of the inputs of one size.
+struct SizeReport {
+    size: usize,
+    cases: usize,
+    elapsed: Duration,
+    /// seed of each failed input, with its statuses
+    failures: Vec<(u64, Statuses)>,
+}
+
+fn failed(statuses: &Statuses) -> bool {
+    statuses
+        .iter()
+        .flatten()
+        .any(|s| matches!(s, Status::Error | Status::Panic | Status::Timeout))
+}
+
+fn format_statuses(statuses: &Statuses) -> String {
+    statuses
+        .iter()
+        .enumerate()
+        .filter_map(|(i, status)| status.map(|s| format!("part {} {}", i + 1, s)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn print_summary(day: u32, reports: &[SizeReport]) {
+    println!("{} stress summary of day {}", "==>".green().bold(), day);
+    for report in reports {
+        let per_case = report.elapsed / report.cases.max(1) as u32;
+        let failures = if report.failures.is_empty() {
+            "no failures".green()
+        } else {
+            format!("{} failed", report.failures.len()).red()
+        };
+        println!(
+            "  {} size {:>7}: {} inputs, {}, {}ms per input",
+            "->".blue().bold(),
+            report.size,
+            report.cases,
+            failures,
+            per_case.as_millis()
+        );
+        for (seed, statuses) in &report.failures {
+            println!(
+                "     seed {}: {}, rerun with --sizes {} --seed {} --count 1",
+                seed,
+                format_statuses(statuses),
+                report.size,
+                seed
+            );
+        }
+    }
+}
+
+/// Runs the day of `args` with `run` on `--count` generated inputs of each
+/// size, then prints for each size the failures and the time per input.
+/// `generates` tells whether a day of a year can generate inputs.
+pub fn stress<F, G>(args: &StressArgs, run: F, generates: G) -> Result<()>
+where
+    F: Fn(&RunArgs, &[Task]) -> io::Result<Vec<Statuses>>,
+    G: Fn(u32, u32) -> bool,
+{
+    if args.run.all || args.run.input.is_some() {
+        bail!("stress runs a single day on generated inputs");
+    }
+    let day = args.run.day.ok_or(anyhow!("missing day"))?;
+    if !generates(args.run.year, day) {
+        bail!("day {} can't generate inputs", day);
+    }
+
+    let mut reports = Vec::new();
+    for &size in &args.sizes {
+        let seeds = args.seed..args.seed + args.count;
+        let tasks: Vec<Task> = seeds
+            .clone()
+            .map(|seed| Task {
+                day,
+                case: TestCase::generated(size, seed),
+            })
+            .collect();
+
+        let start_time = Instant::now();
+        let statuses = run(&args.run, &tasks)?;
+        let failures = seeds
+            .zip(statuses)
+            .filter(|(_, statuses)| failed(statuses))
+            .collect();
+        reports.push(SizeReport {
+            size,
+            cases: tasks.len(),
+            elapsed: start_time.elapsed(),
+            failures,
+        });
+    }
+
+    print_summary(day, &reports);
+    if reports.iter().any(|r| !r.failures.is_empty()) {
+        bail!("some generated inputs failed");
+    }
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use anyhow::{anyhow, bail, Result};
+use colored::Colorize;
+
+use crate::args::SubmitArgs;
+use crate::history::{History, Verdict};
+use crate::test_case::{day_dir, TestCase};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Response {
+    Verdict(Verdict),
+    Wait(Option<String>),
+    AlreadySolved,
+    Unknown,
+}
+
+/// Interprets the HTML page returned after posting an answer.
+pub fn parse_response(body: &str) -> Response {
+    if body.contains("That's the right answer") {
+        Response::Verdict(Verdict::Correct)
+    } else if body.contains("You gave an answer too recently") {
+        let wait = body
+            .split_once("You have ")
+            .and_then(|(_, rest)| rest.split_once(" left to wait"))
+            .map(|(time, _)| time.to_string());
+        Response::Wait(wait)
+    } else if body.contains("Did you already complete it") {
+        Response::AlreadySolved
+    } else if body.contains("That's not the right answer") {
+        if body.contains("your answer is too high") {
+            Response::Verdict(Verdict::TooHigh)
+        } else if body.contains("your answer is too low") {
+            Response::Verdict(Verdict::TooLow)
+        } else {
+            Response::Verdict(Verdict::Wrong)
+        }
+    } else {
+        Response::Unknown
+    }
+}
+
+fn post_answer(args: &SubmitArgs, answer: &str) -> Result<String> {
+    let url = format!(
+        "{}/{}/day/{}/answer",
+        args.endpoint.trim_end_matches('/'),
+        args.year,
+        args.day
+    );
+    let response = ureq::post(&url)
+        .set("Cookie", &format!("session={}", args.session))
+        .send_form(&[("level", &args.part.to_string()), ("answer", answer)])
+        .map_err(|e| anyhow!("submission failed: {}", e))?;
+
+    Ok(response.into_string()?)
+}
+
+pub fn submit(args: &SubmitArgs) -> Result<()> {
+    let answer = match &args.answer {
+        Some(answer) => answer.trim().to_string(),
+        None => {
+            let path = day_dir(&args.input_dir, args.year, args.day).join("input.txt");
+            let case = TestCase::load(&path)?;
+            case.output(args.part)
+                .map(|a| a.trim().to_string())
+                .ok_or(anyhow!("no stored answer for part {}, run with --store-output", args.part))?
+        }
+    };
+
+    let mut history = History::load(&args.input_dir, args.year, args.day)?;
+    if let Some(solved) = history.solved(args.part) {
+        bail!("part {} already solved with answer {}", args.part, solved);
+    }
+    if let Some(reason) = history.check(args.part, &answer) {
+        if !args.force {
+            bail!("refusing to submit: {}", reason);
+        }
+        println!("  {} {}", "->".yellow().bold(), reason);
+    }
+
+    println!(
+        "{} submitting {} for day {} of {} part {}",
+        "==>".green().bold(),
+        answer,
+        args.day,
+        args.year,
+        args.part
+    );
+
+    match parse_response(&post_answer(args, &answer)?) {
+        Response::Verdict(verdict) => {
+            history.record(args.part, &answer, verdict)?;
+            let message = match verdict {
+                Verdict::Correct => "CORRECT".green(),
+                Verdict::TooHigh => "TOO HIGH".red(),
+                Verdict::TooLow => "TOO LOW".red(),
+                Verdict::Wrong => " WRONG ".red(),
+            };
+            println!("  {} {}", "->".blue().bold(), message);
+        }
+        Response::Wait(time) => {
+            println!(
+                "  {} answered too recently, wait {}",
+                "->".yellow().bold(),
+                time.as_deref().unwrap_or("a bit")
+            );
+        }
+        Response::AlreadySolved => {
+            println!("  {} part already solved", "->".yellow().bold());
+        }
+        Response::Unknown => bail!("unrecognized response from server"),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_verdicts() {
+        assert_eq!(
+            parse_response("<p>That's the right answer! You are one gold star closer.</p>"),
+            Response::Verdict(Verdict::Correct)
+        );
+        assert_eq!(
+            parse_response("<p>That's not the right answer; your answer is too high.</p>"),
+            Response::Verdict(Verdict::TooHigh)
+        );
+        assert_eq!(
+            parse_response("<p>That's not the right answer; your answer is too low.</p>"),
+            Response::Verdict(Verdict::TooLow)
+        );
+        assert_eq!(
+            parse_response("<p>That's not the right answer. If you're stuck...</p>"),
+            Response::Verdict(Verdict::Wrong)
+        );
+        assert_eq!(
+            parse_response("<p>You don't seem to be solving the right level.  Did you already complete it?</p>"),
+            Response::AlreadySolved
+        );
+        assert_eq!(parse_response("<html></html>"), Response::Unknown);
+    }
+
+    #[test]
+    fn parse_wait() {
+        assert_eq!(
+            parse_response("<p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 2s left to wait.</p>"),
+            Response::Wait(Some("4m 2s".into()))
+        );
+    }
+}
@@ -1,10 +1,14 @@
 use std::{path::{PathBuf, Path}, fs};
-use std::io::Result;
+use std::io::{self, Read, Result};
+
+const STDIN: &str = "-";
 
 pub struct TestCase {
     path: PathBuf,
     input: String,
     output: (Option<String>, Option<String>),
+    /// size and seed of a generated input, made by the runner
+    generated: Option<(usize, u64)>,
 }
 
 impl TestCase {
@@ -14,23 +18,62 @@ impl TestCase {
         if ans_path.exists() {
             let ans_content = fs::read_to_string(ans_path)?;
             let mut lines = ans_content.lines();
-            output = (
-                lines.next().map(String::from),
-                lines.next().map(String::from),
-            );
+            let mut next_answer = || lines.next().filter(|l| !l.is_empty()).map(String::from);
+            output = (next_answer(), next_answer());
         }
 
         Ok(TestCase {
             path: path.into(),
             input: fs::read_to_string(path)?,
             output,
+            generated: None,
+        })
+    }
+
+    pub fn from_stdin() -> Result<Self> {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+
+        Ok(TestCase {
+            path: STDIN.into(),
+            input,
+            output: (None, None),
+            generated: None,
         })
     }
 
+    /// Case whose input the runner generates with the day's
+    /// [`crate::problem::AoCProblem::generate_input`].
+    pub fn generated(size: usize, seed: u64) -> Self {
+        TestCase {
+            path: format!("size-{}-seed-{}", size, seed).into(),
+            input: String::new(),
+            output: (None, None),
+            generated: Some((size, seed)),
+        }
+    }
+
+    /// Size and seed of the input to generate, for generated cases.
+    pub fn generated_input(&self) -> Option<(usize, u64)> {
+        self.generated
+    }
+
+    fn is_stdin(&self) -> bool {
+        self.path == Path::new(STDIN)
+    }
+
     pub fn name(&self) -> String {
+        if self.is_stdin() {
+            return "stdin".into();
+        }
         self.path.file_name().unwrap_or_default().to_str().map(str::to_string).unwrap_or_default()
     }
 
+    /// Whether this is the personal puzzle input, the one answers are submitted for.
+    pub fn is_puzzle_input(&self) -> bool {
+        self.path.file_stem().is_some_and(|s| s == "input")
+    }
+
     pub fn input(&self) -> &str {
         &self.input
     }
@@ -43,18 +86,49 @@ impl TestCase {
         }
     }
 
-    pub fn write_output(&self, part_1: &str, part_2: &str) -> Result<()> {
+    /// Stores the answers on the .ans file, keeping the existing answer of a
+    /// part when `None` is given for it.
+    pub fn write_output(&self, part_1: Option<&str>, part_2: Option<&str>) -> Result<()> {
+        if self.is_stdin() {
+            return Err(io::Error::other("cannot store the output of stdin"));
+        }
+        if self.generated.is_some() {
+            return Err(io::Error::other("cannot store the output of a generated input"));
+        }
         let ans_path = PathBuf::from(&self.path).with_extension("ans");
+        let part_1 = part_1.or(self.output(1)).unwrap_or_default();
+        let part_2 = part_2.or(self.output(2)).unwrap_or_default();
         fs::write(ans_path, format!("{}\n{}\n", part_1, part_2))
     }
 }
 
-pub fn load_test_cases(day: u32) -> Result<Vec<TestCase>> {
-    let dir = std::fs::read_dir(format!("input/{:02}", day))?;
+/// Directory holding the inputs of a day under the `root` input directory.
+pub fn day_dir(root: &Path, year: u32, day: u32) -> PathBuf {
+    root.join(year.to_string()).join(format!("{:02}", day))
+}
+
+pub fn load_test_cases(root: &Path, year: u32, day: u32) -> Result<Vec<TestCase>> {
+    load_dir(&day_dir(root, year, day))
+}
+
+/// Loads the cases of an explicit input: a single file, every input in a
+/// directory or `-` for stdin.
+pub fn load_input(path: &Path) -> Result<Vec<TestCase>> {
+    if path == Path::new(STDIN) {
+        Ok(vec![TestCase::from_stdin()?])
+    } else if path.is_dir() {
+        load_dir(path)
+    } else {
+        Ok(vec![TestCase::load(path)?])
+    }
+}
+
+fn load_dir(dir: &Path) -> Result<Vec<TestCase>> {
+    let dir = fs::read_dir(dir)?;
     let mut paths = Vec::new();
     for file in dir {
         let path = file?.path();
-        if !path.extension().is_some_and(|e| e == "ans") {
+        if path.extension().is_none_or(|e| e != "ans" && e != "log") {
             paths.push(path);
         }
     }
@@ -62,3 +136,53 @@ pub fn load_test_cases(day: u32) -> Result<Vec<TestCase>> {
     paths.sort();
     paths.iter().map(|p| TestCase::load(p)).collect()
 }
+
+/// Normalizes an input before parsing: LF line endings, no trailing whitespace
+/// on the lines nor blank lines at the end, and a final newline.
+///
+/// Returns the normalized input and what was changed.
+pub fn normalize(input: &str) -> (String, Vec<&'static str>) {
+    let mut changes = Vec::new();
+    if input.contains('\r') {
+        changes.push("CRLF line endings");
+    }
+
+    let mut lines: Vec<&str> = input.lines().collect();
+    let trimmed: Vec<&str> = lines.iter().map(|l| l.trim_end()).collect();
+    if trimmed != lines {
+        changes.push("trailing whitespace");
+    }
+    lines = trimmed;
+
+    let content = lines.iter().rposition(|l| !l.is_empty()).map_or(0, |i| i + 1);
+    if content < lines.len() {
+        changes.push("blank lines at the end");
+        lines.truncate(content);
+    } else if !input.is_empty() && !input.ends_with('\n') {
+        changes.push("missing final newline");
+    }
+
+    let mut result = lines.join("\n");
+    if !lines.is_empty() {
+        result.push('\n');
+    }
+    (result, changes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn normalize_input() {
+        assert_eq!(normalize("a\nb\n"), ("a\nb\n".into(), vec![]));
+        assert_eq!(normalize(""), ("".into(), vec![]));
+        assert_eq!(normalize("a\r\nb\r\n"), ("a\nb\n".into(), vec!["CRLF line endings"]));
+        assert_eq!(
+            normalize("a  \nb"),
+            ("a\nb\n".into(), vec!["trailing whitespace", "missing final newline"])
+        );
+        assert_eq!(normalize("a\n\n\n"), ("a\n".into(), vec!["blank lines at the end"]));
+        assert_eq!(normalize("  a\n\nb\n"), ("  a\n\nb\n".into(), vec![]));
+    }
+}
@@ -0,0 +1,142 @@
+//! Lightweight instrumentation for solvers: messages logged with
+//! [`debug!`](crate::debug!) or [`trace!`](crate::trace!) and counters
+//! incremented with [`count`], shown in the report of a part when the runner
+//! is given `-v` or `-vv`.
+//!
+//! Like [`crate::memory`], everything is kept per thread, so cases running in
+//! parallel don't mix their logs, and nothing is recorded unless the runner
+//! enabled it.
+
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+
+/// Messages kept per part, the others are only counted.
+const MAX_EVENTS: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// shown with `-v`, with the counters
+    Debug = 1,
+    /// shown with `-vv`
+    Trace = 2,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TraceLog {
+    pub events: Vec<(Level, String)>,
+    /// events logged past the first [`MAX_EVENTS`]
+    pub dropped: usize,
+    pub counters: BTreeMap<&'static str, u64>,
+}
+
+impl TraceLog {
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty() && self.counters.is_empty()
+    }
+}
+
+thread_local! {
+    static VERBOSITY: Cell<u8> = const { Cell::new(0) };
+    static LOG: RefCell<TraceLog> = RefCell::new(TraceLog::default());
+}
+
+/// Whether events of `level` are recorded on the current thread.
+pub fn enabled(level: Level) -> bool {
+    VERBOSITY.with(Cell::get) >= level as u8
+}
+
+/// Records a message. Use [`debug!`](crate::debug!) or
+/// [`trace!`](crate::trace!) instead, which only format it when recorded.
+pub fn event(level: Level, message: String) {
+    if !enabled(level) {
+        return;
+    }
+    LOG.with_borrow_mut(|log| {
+        if log.events.len() < MAX_EVENTS {
+            log.events.push((level, message));
+        } else {
+            log.dropped += 1;
+        }
+    });
+}
+
+/// Adds `n` to the counter `name`, when `-v` is given.
+pub fn count(name: &'static str, n: u64) {
+    if enabled(Level::Debug) {
+        LOG.with_borrow_mut(|log| *log.counters.entry(name).or_default() += n);
+    }
+}
+
+/// Runs `f` recording on the current thread the events up to `verbosity`, 0
+/// for none, and returns them.
+pub fn collect<R>(verbosity: u8, f: impl FnOnce() -> R) -> (R, TraceLog) {
+    let previous = VERBOSITY.with(|v| v.replace(verbosity));
+    let outer = LOG.with_borrow_mut(std::mem::take);
+
+    let result = f();
+
+    VERBOSITY.with(|v| v.set(previous));
+    let log = LOG.with_borrow_mut(|log| std::mem::replace(log, outer));
+    (result, log)
+}
+
+/// Logs a message shown with `-v`, formatted like `format!`.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::trace::enabled($crate::trace::Level::Debug) {
+            $crate::trace::event($crate::trace::Level::Debug, format!($($arg)*));
+        }
+    };
+}
+
+/// Logs a message shown with `-vv`, formatted like `format!`.
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::trace::enabled($crate::trace::Level::Trace) {
+            $crate::trace::event($crate::trace::Level::Trace, format!($($arg)*));
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn levels() {
+        let run = || {
+            crate::debug!("debug {}", 1);
+            crate::trace!("trace {}", 2);
+            count("calls", 2);
+            count("calls", 3);
+        };
+
+        let ((), log) = collect(0, run);
+        assert!(log.is_empty());
+
+        let ((), log) = collect(1, run);
+        assert_eq!(log.events, [(Level::Debug, "debug 1".to_string())]);
+        assert_eq!(log.counters.get("calls"), Some(&5));
+
+        let ((), log) = collect(2, run);
+        assert_eq!(log.events.len(), 2);
+        assert!(!enabled(Level::Debug));
+    }
+
+    #[test]
+    fn nested() {
+        let (inner, outer) = collect(1, || {
+            count("outer", 1);
+            let ((), inner) = collect(1, || count("inner", 1));
+            count("outer", 1);
+            inner
+        });
+        assert_eq!(inner.counters.keys().copied().collect::<Vec<_>>(), ["inner"]);
+        assert_eq!(outer.counters.get("outer"), Some(&2));
+
+        let ((), log) = collect(1, || (0..MAX_EVENTS + 5).for_each(|i| crate::debug!("{}", i)));
+        assert_eq!((log.events.len(), log.dropped), (MAX_EVENTS, 5));
+    }
+}
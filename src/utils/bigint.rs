@@ -0,0 +1,470 @@
+use std::cmp::Ordering;
+use std::fmt::{self, Display};
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
+use std::str::FromStr;
+
+use anyhow::{anyhow, Error, Result};
+
+/// Arbitrary precision signed integer, for answers and intermediate values
+/// that don't fit an `i128`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    /// base 2^32 digits, least significant first, without leading zeros
+    digits: Vec<u32>,
+}
+
+fn trim(digits: &mut Vec<u32>) {
+    while digits.last() == Some(&0) {
+        digits.pop();
+    }
+}
+
+fn cmp_magnitude(a: &[u32], b: &[u32]) -> Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut result = Vec::with_capacity(a.len() + 1);
+    let mut carry = 0u64;
+    for (i, &digit) in a.iter().enumerate() {
+        let sum = digit as u64 + *b.get(i).unwrap_or(&0) as u64 + carry;
+        result.push(sum as u32);
+        carry = sum >> 32;
+    }
+    if carry > 0 {
+        result.push(carry as u32);
+    }
+    result
+}
+
+/// `a - b`, for `a` not smaller than `b`.
+fn sub_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for (i, &digit) in a.iter().enumerate() {
+        let mut difference = digit as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+        borrow = (difference < 0) as i64;
+        difference += borrow << 32;
+        result.push(difference as u32);
+    }
+    trim(&mut result);
+    result
+}
+
+fn mul_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = vec![0u32; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, &y) in b.iter().enumerate() {
+            let product = x as u64 * y as u64 + result[i + j] as u64 + carry;
+            result[i + j] = product as u32;
+            carry = product >> 32;
+        }
+        result[i + b.len()] = carry as u32;
+    }
+    trim(&mut result);
+    result
+}
+
+fn div_rem_small(a: &[u32], divisor: u32) -> (Vec<u32>, u32) {
+    let mut quotient = vec![0u32; a.len()];
+    let mut remainder = 0u64;
+    for (i, &digit) in a.iter().enumerate().rev() {
+        let current = (remainder << 32) | digit as u64;
+        quotient[i] = (current / divisor as u64) as u32;
+        remainder = current % divisor as u64;
+    }
+    trim(&mut quotient);
+    (quotient, remainder as u32)
+}
+
+/// Quotient and remainder of the magnitudes, for a non zero `b`.
+fn div_rem_magnitude(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if b.len() == 1 {
+        let (quotient, remainder) = div_rem_small(a, b[0]);
+        let mut remainder = vec![remainder];
+        trim(&mut remainder);
+        return (quotient, remainder);
+    }
+
+    // binary long division, fast enough for the sizes of puzzles
+    let mut quotient = vec![0u32; a.len()];
+    let mut remainder: Vec<u32> = Vec::new();
+    for bit in (0..a.len() * 32).rev() {
+        let mut carry = (a[bit / 32] >> (bit % 32)) & 1;
+        for digit in remainder.iter_mut() {
+            let next_carry = *digit >> 31;
+            *digit = (*digit << 1) | carry;
+            carry = next_carry;
+        }
+        if carry > 0 {
+            remainder.push(carry);
+        }
+
+        if cmp_magnitude(&remainder, b) != Ordering::Less {
+            remainder = sub_magnitude(&remainder, b);
+            quotient[bit / 32] |= 1 << (bit % 32);
+        }
+    }
+    trim(&mut quotient);
+    (quotient, remainder)
+}
+
+impl BigInt {
+    fn from_parts(negative: bool, mut digits: Vec<u32>) -> Self {
+        trim(&mut digits);
+        let negative = negative && !digits.is_empty();
+        BigInt { negative, digits }
+    }
+
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn abs(&self) -> Self {
+        BigInt::from_parts(false, self.digits.clone())
+    }
+
+    pub fn pow(&self, mut exp: u32) -> Self {
+        let (mut base, mut result) = (self.clone(), BigInt::from(1));
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = &result * &base;
+            }
+            base = &base * &base;
+            exp >>= 1;
+        }
+        result
+    }
+
+    /// Quotient rounded towards zero and remainder with the sign of `self`,
+    /// like the primitive integers. `None` when dividing by zero.
+    pub fn div_rem(&self, other: &Self) -> Option<(Self, Self)> {
+        if other.is_zero() {
+            return None;
+        }
+        let (quotient, remainder) = div_rem_magnitude(&self.digits, &other.digits);
+        Some((
+            BigInt::from_parts(self.negative != other.negative, quotient),
+            BigInt::from_parts(self.negative, remainder),
+        ))
+    }
+
+    /// The value as an `i128`, `None` if it doesn't fit.
+    pub fn to_i128(&self) -> Option<i128> {
+        if self.digits.len() > 4 {
+            return None;
+        }
+        let magnitude = self.digits.iter().rev().fold(0u128, |a, &d| (a << 32) | d as u128);
+        if self.negative {
+            0i128.checked_sub_unsigned(magnitude)
+        } else {
+            i128::try_from(magnitude).ok()
+        }
+    }
+}
+
+impl From<u128> for BigInt {
+    fn from(mut n: u128) -> Self {
+        let mut digits = Vec::new();
+        while n > 0 {
+            digits.push(n as u32);
+            n >>= 32;
+        }
+        BigInt::from_parts(false, digits)
+    }
+}
+
+impl From<i128> for BigInt {
+    fn from(n: i128) -> Self {
+        let magnitude = BigInt::from(n.unsigned_abs());
+        BigInt::from_parts(n < 0, magnitude.digits)
+    }
+}
+
+macro_rules! from_primitive {
+    ($via:ty: $($t:ty),*) => {
+        $(impl From<$t> for BigInt {
+            fn from(n: $t) -> Self {
+                BigInt::from(n as $via)
+            }
+        })*
+    };
+}
+
+from_primitive!(u128: u8, u16, u32, u64, usize);
+from_primitive!(i128: i8, i16, i32, i64, isize);
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_magnitude(&self.digits, &other.digits),
+            (true, true) => cmp_magnitude(&other.digits, &self.digits),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.digits.clone())
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.digits)
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::from_parts(self.negative, add_magnitude(&self.digits, &other.digits));
+        }
+        // different signs: the larger magnitude gives the sign
+        match cmp_magnitude(&self.digits, &other.digits) {
+            Ordering::Less => {
+                BigInt::from_parts(other.negative, sub_magnitude(&other.digits, &self.digits))
+            }
+            _ => BigInt::from_parts(self.negative, sub_magnitude(&self.digits, &other.digits)),
+        }
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &BigInt) -> BigInt {
+        self + &-other
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: &BigInt) -> BigInt {
+        let digits = mul_magnitude(&self.digits, &other.digits);
+        BigInt::from_parts(self.negative != other.negative, digits)
+    }
+}
+
+impl Div for &BigInt {
+    type Output = BigInt;
+
+    /// Panics when dividing by zero, like the primitive integers.
+    fn div(self, other: &BigInt) -> BigInt {
+        self.div_rem(other).expect("attempt to divide by zero").0
+    }
+}
+
+impl Rem for &BigInt {
+    type Output = BigInt;
+
+    fn rem(self, other: &BigInt) -> BigInt {
+        self.div_rem(other)
+            .expect("attempt to calculate the remainder with a divisor of zero")
+            .1
+    }
+}
+
+/// Operators on owned values and mixed with references, forwarded to the
+/// ones on references.
+macro_rules! forward_ops {
+    ($($trait:ident $method:ident $assign_trait:ident $assign_method:ident),*) => {
+        $(
+            impl $trait for BigInt {
+                type Output = BigInt;
+
+                fn $method(self, other: BigInt) -> BigInt {
+                    (&self).$method(&other)
+                }
+            }
+
+            impl $trait<&BigInt> for BigInt {
+                type Output = BigInt;
+
+                fn $method(self, other: &BigInt) -> BigInt {
+                    (&self).$method(other)
+                }
+            }
+
+            impl $assign_trait<&BigInt> for BigInt {
+                fn $assign_method(&mut self, other: &BigInt) {
+                    *self = (&*self).$method(other);
+                }
+            }
+
+            impl $assign_trait for BigInt {
+                fn $assign_method(&mut self, other: BigInt) {
+                    *self = (&*self).$method(&other);
+                }
+            }
+        )*
+    };
+}
+
+forward_ops!(
+    Add add AddAssign add_assign,
+    Sub sub SubAssign sub_assign,
+    Mul mul MulAssign mul_assign
+);
+
+impl Div for BigInt {
+    type Output = BigInt;
+
+    fn div(self, other: BigInt) -> BigInt {
+        &self / &other
+    }
+}
+
+impl Rem for BigInt {
+    type Output = BigInt;
+
+    fn rem(self, other: BigInt) -> BigInt {
+        &self % &other
+    }
+}
+
+impl Sum for BigInt {
+    fn sum<I: Iterator<Item = BigInt>>(iter: I) -> Self {
+        iter.fold(BigInt::zero(), |a, b| a + b)
+    }
+}
+
+impl Product for BigInt {
+    fn product<I: Iterator<Item = BigInt>>(iter: I) -> Self {
+        iter.fold(BigInt::from(1), |a, b| a * b)
+    }
+}
+
+impl Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return f.write_str("0");
+        }
+
+        // groups of 9 decimal digits, least significant first
+        let mut groups = Vec::new();
+        let mut rest = self.digits.clone();
+        while !rest.is_empty() {
+            let (quotient, group) = div_rem_small(&rest, 1_000_000_000);
+            groups.push(group);
+            rest = quotient;
+        }
+
+        let mut text = String::new();
+        if self.negative {
+            text.push('-');
+        }
+        text.push_str(&groups.pop().unwrap_or_default().to_string());
+        for group in groups.iter().rev() {
+            text.push_str(&format!("{:09}", group));
+        }
+        f.pad(&text)
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (negative, decimal) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        if decimal.is_empty() || !decimal.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(anyhow!("invalid integer {}", s));
+        }
+
+        let mut digits = Vec::new();
+        for chunk in decimal.as_bytes().chunks(9) {
+            let value: u32 = std::str::from_utf8(chunk)?.parse()?;
+            let scale = 10u32.pow(chunk.len() as u32);
+            digits = mul_magnitude(&digits, &[scale]);
+            digits = add_magnitude(&digits, &[value]);
+            trim(&mut digits);
+        }
+        Ok(BigInt::from_parts(negative, digits))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn big(s: &str) -> BigInt {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_and_format() {
+        for s in ["0", "42", "-42", "4294967296", "-123456789012345678901234567890"] {
+            assert_eq!(big(s).to_string(), s);
+        }
+        assert_eq!(big("-0"), BigInt::zero());
+        assert_eq!(big("+007").to_string(), "7");
+        assert!("12a".parse::<BigInt>().is_err());
+        assert!("-".parse::<BigInt>().is_err());
+        assert_eq!(format!("{:>5}", BigInt::from(-3)), "   -3");
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = BigInt::from(i128::MAX);
+        let b = big("-98765432109876543210");
+        assert_eq!((&a + &a).to_string(), "340282366920938463463374607431768211454");
+        assert_eq!((&b - &a).to_string(), "-170141183460469231830452735825760648937");
+        assert_eq!(&b + &(-&b), BigInt::zero());
+        assert_eq!((&a * &b) / b.clone(), a);
+        assert_eq!(BigInt::from(2).pow(100).to_string(), "1267650600228229401496703205376");
+        assert_eq!([1, 2, 3].map(BigInt::from).into_iter().product::<BigInt>(), BigInt::from(6));
+
+        let mut sum = BigInt::from(u64::MAX);
+        sum += BigInt::from(1);
+        assert_eq!(sum.to_string(), "18446744073709551616");
+    }
+
+    #[test]
+    fn division() {
+        for (a, b) in [(7i128, 2i128), (-7, 2), (7, -2), (-7, -2), (1 << 100, 3 << 40)] {
+            let (q, r) = BigInt::from(a).div_rem(&BigInt::from(b)).unwrap();
+            assert_eq!((q.to_i128(), r.to_i128()), (Some(a / b), Some(a % b)));
+        }
+        assert_eq!(BigInt::from(1).div_rem(&BigInt::zero()), None);
+        assert_eq!(BigInt::from(i128::MIN).to_i128(), Some(i128::MIN));
+        assert_eq!((BigInt::from(i128::MAX) + BigInt::from(1)).to_i128(), None);
+    }
+
+    #[test]
+    fn ordering() {
+        let mut values = [5, -3, 0, 1i64 << 40, -(1 << 40)].map(BigInt::from);
+        values.sort();
+        let expected = [-(1i64 << 40), -3, 0, 5, 1 << 40].map(BigInt::from);
+        assert_eq!(values, expected);
+    }
+}
@@ -0,0 +1,86 @@
+//! Helpers for the days parsed with pest.
+//!
+//! `grammar.pest` holds the rules shared by every grammar (`string`, `number`,
+//! `whitespace`, `newline`, ...). A day includes it before its own grammar:
+//!
+//! ```ignore
+//! #[derive(Parser)]
+//! #[grammar = "src/utils/grammar.pest"]
+//! #[grammar = "src/y2023/day06.pest"]
+//! struct Day06Parser;
+//! ```
+
+use pest::error::{Error, ErrorVariant};
+use pest::iterators::Pair;
+use pest::RuleType;
+
+use crate::utils::parse_error::ParseError;
+
+/// Value built from a node of a pest parse tree.
+pub trait FromPair<R: RuleType>: Sized {
+    fn from_pair(pair: Pair<'_, R>) -> Result<Self, ParseError>;
+}
+
+/// Error pointing at the text of `pair`.
+pub fn error<R: RuleType>(pair: &Pair<'_, R>, message: impl Into<String>) -> ParseError {
+    let variant = ErrorVariant::CustomError {
+        message: message.into(),
+    };
+    Error::<R>::new_from_span(variant, pair.as_span()).into()
+}
+
+macro_rules! from_str_pair {
+    ($($t:ty),*) => {
+        $(impl<R: RuleType> FromPair<R> for $t {
+            fn from_pair(pair: Pair<'_, R>) -> Result<Self, ParseError> {
+                pair.as_str()
+                    .trim()
+                    .parse()
+                    .map_err(|_| error(&pair, concat!("invalid ", stringify!($t))))
+            }
+        })*
+    };
+}
+
+from_str_pair!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f64);
+
+impl<R: RuleType> FromPair<R> for String {
+    fn from_pair(pair: Pair<'_, R>) -> Result<Self, ParseError> {
+        Ok(pair.as_str().into())
+    }
+}
+
+/// One value for each child of the node.
+impl<R: RuleType, T: FromPair<R>> FromPair<R> for Vec<T> {
+    fn from_pair(pair: Pair<'_, R>) -> Result<Self, ParseError> {
+        pair.into_inner().map(T::from_pair).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pest::Parser;
+    use pest_derive::Parser;
+
+    use super::*;
+
+    #[derive(Parser)]
+    #[grammar = "src/utils/grammar.pest"]
+    #[grammar_inline = "list = { number ~ (whitespace ~ number)* ~ newline }"]
+    struct ListParser;
+
+    fn parse<T: FromPair<Rule>>(input: &str) -> Result<T, ParseError> {
+        let pair = ListParser::parse(Rule::list, input).map_err(ParseError::from)?;
+        T::from_pair(pair.into_iter().next().unwrap())
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(parse::<Vec<i32>>("1 -2 3\n"), Ok(vec![1, -2, 3]));
+        assert_eq!(parse::<Vec<u64>>("4\r\n"), Ok(vec![4]));
+        assert_eq!(parse::<String>("1 2\n"), Ok("1 2\n".into()));
+
+        let error = parse::<Vec<u8>>("1 300\n").unwrap_err();
+        assert_eq!((error.column, error.message.as_str()), (2, "invalid u8"));
+    }
+}
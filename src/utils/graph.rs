@@ -0,0 +1,506 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+use crate::trace;
+
+/// Graph with weighted edges between nodes of type `N`.
+///
+/// Nodes are kept in insertion order, so every traversal is deterministic.
+#[derive(Debug, Clone)]
+pub struct Graph<N> {
+    nodes: Vec<N>,
+    indices: HashMap<N, usize>,
+    edges: Vec<Vec<(usize, u64)>>,
+}
+
+impl<N> Default for Graph<N> {
+    fn default() -> Self {
+        Graph {
+            nodes: Vec::new(),
+            indices: HashMap::new(),
+            edges: Vec::new(),
+        }
+    }
+}
+
+impl<N: Eq + Hash + Clone> Graph<N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `node` if it's missing, returning its index.
+    pub fn add_node(&mut self, node: N) -> usize {
+        if let Some(&index) = self.indices.get(&node) {
+            return index;
+        }
+
+        self.nodes.push(node.clone());
+        self.edges.push(Vec::new());
+        self.indices.insert(node, self.nodes.len() - 1);
+        self.nodes.len() - 1
+    }
+
+    /// Adds an edge from `from` to `to`, adding the nodes if needed.
+    pub fn add_edge(&mut self, from: N, to: N, weight: u64) {
+        let (from, to) = (self.add_node(from), self.add_node(to));
+        self.edges[from].push((to, weight));
+    }
+
+    /// Adds an edge in both directions.
+    pub fn add_undirected_edge(&mut self, a: N, b: N, weight: u64) {
+        self.add_edge(a.clone(), b.clone(), weight);
+        self.add_edge(b, a, weight);
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.indices.contains_key(node)
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &N> {
+        self.nodes.iter()
+    }
+
+    /// Nodes reached by the edges leaving `node`, with the edges weight.
+    pub fn neighbours<'a>(&'a self, node: &N) -> impl Iterator<Item = (&'a N, u64)> + 'a {
+        self.indices
+            .get(node)
+            .map(|&i| self.edges[i].as_slice())
+            .unwrap_or_default()
+            .iter()
+            .map(|&(j, weight)| (&self.nodes[j], weight))
+    }
+
+    /// Groups of nodes linked by edges, whatever their direction.
+    pub fn connected_components(&self) -> Vec<Vec<N>> {
+        let mut undirected = vec![Vec::new(); self.len()];
+        for (i, edges) in self.edges.iter().enumerate() {
+            for &(j, _) in edges {
+                undirected[i].push(j);
+                undirected[j].push(i);
+            }
+        }
+
+        let mut seen = vec![false; self.len()];
+        let mut components = Vec::new();
+        for start in 0..self.len() {
+            if seen[start] {
+                continue;
+            }
+
+            seen[start] = true;
+            let mut component = Vec::new();
+            let mut stack = vec![start];
+            while let Some(i) = stack.pop() {
+                component.push(self.nodes[i].clone());
+                for &j in &undirected[i] {
+                    if !seen[j] {
+                        seen[j] = true;
+                        stack.push(j);
+                    }
+                }
+            }
+            components.push(component);
+        }
+        components
+    }
+
+    fn topological_indices(&self) -> Option<Vec<usize>> {
+        let mut incoming = vec![0; self.len()];
+        for edges in &self.edges {
+            for &(j, _) in edges {
+                incoming[j] += 1;
+            }
+        }
+
+        let mut queue: VecDeque<usize> = (0..self.len()).filter(|&i| incoming[i] == 0).collect();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(i) = queue.pop_front() {
+            order.push(i);
+            for &(j, _) in &self.edges[i] {
+                incoming[j] -= 1;
+                if incoming[j] == 0 {
+                    queue.push_back(j);
+                }
+            }
+        }
+
+        (order.len() == self.len()).then_some(order)
+    }
+
+    /// Nodes ordered so that every edge goes forward, `None` if there's a cycle.
+    pub fn topological_order(&self) -> Option<Vec<N>> {
+        let order = self.topological_indices()?;
+        Some(order.into_iter().map(|i| self.nodes[i].clone()).collect())
+    }
+
+    /// Nodes of a cycle following the edges direction, if there is one.
+    pub fn find_cycle(&self) -> Option<Vec<N>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum State {
+            New,
+            OnPath,
+            Done,
+        }
+
+        let mut state = vec![State::New; self.len()];
+        for start in 0..self.len() {
+            if state[start] != State::New {
+                continue;
+            }
+
+            // path of nodes being visited, with the next edge to follow of each
+            let mut path = vec![(start, 0)];
+            state[start] = State::OnPath;
+            while let Some((i, edge)) = path.last_mut() {
+                let i = *i;
+                let Some(&(j, _)) = self.edges[i].get(*edge) else {
+                    state[i] = State::Done;
+                    path.pop();
+                    continue;
+                };
+
+                *edge += 1;
+                match state[j] {
+                    State::New => {
+                        state[j] = State::OnPath;
+                        path.push((j, 0));
+                    }
+                    State::OnPath => {
+                        let from = path.iter().position(|&(k, _)| k == j).unwrap_or_default();
+                        return Some(
+                            path[from..]
+                                .iter()
+                                .map(|&(k, _)| self.nodes[k].clone())
+                                .collect(),
+                        );
+                    }
+                    State::Done => {}
+                }
+            }
+        }
+        None
+    }
+
+    /// Length of the longest path from `from` to every node it reaches.
+    ///
+    /// `None` if the graph has a cycle, where paths can be arbitrarily long.
+    pub fn longest_paths(&self, from: &N) -> Option<HashMap<N, u64>> {
+        let order = self.topological_indices()?;
+        let mut lengths: Vec<Option<u64>> = vec![None; self.len()];
+        if let Some(&start) = self.indices.get(from) {
+            lengths[start] = Some(0);
+        }
+
+        for i in order {
+            let Some(length) = lengths[i] else { continue };
+            for &(j, weight) in &self.edges[i] {
+                let candidate = length + weight;
+                if lengths[j].is_none_or(|current| current < candidate) {
+                    lengths[j] = Some(candidate);
+                }
+            }
+        }
+
+        Some(
+            lengths
+                .into_iter()
+                .enumerate()
+                .filter_map(|(i, length)| Some((self.nodes[i].clone(), length?)))
+                .collect(),
+        )
+    }
+
+    /// Global minimum cut of the graph, seen as undirected, with the
+    /// Stoer–Wagner algorithm: the total weight of the cut edges and the nodes
+    /// on one side. `None` with less than two nodes.
+    ///
+    /// Panics if two nodes are linked both ways with different weights, which
+    /// isn't an undirected graph.
+    pub fn min_cut(&self) -> Option<(u64, Vec<N>)> {
+        let n = self.len();
+        if n < 2 {
+            return None;
+        }
+
+        let mut adjacency: Vec<HashMap<usize, u64>> = vec![HashMap::new(); n];
+        for (i, edges) in self.edges.iter().enumerate() {
+            for &(j, weight) in edges.iter().filter(|&&(j, _)| j != i) {
+                *adjacency[i].entry(j).or_default() += weight;
+            }
+        }
+        // an undirected edge is stored once from each end, a directed one only from its start
+        for i in 0..n {
+            for (j, weight) in adjacency[i].clone() {
+                let reverse = *adjacency[j].entry(i).or_insert(weight);
+                assert_eq!(reverse, weight, "edges of different weights in both directions");
+            }
+        }
+
+        let mut groups: Vec<Vec<usize>> = (0..n).map(|i| vec![i]).collect();
+        let mut active: Vec<usize> = (0..n).collect();
+        let mut best: Option<(u64, Vec<usize>)> = None;
+        while active.len() > 1 {
+            // maximum adjacency ordering, the last two nodes give the phase cut
+            let mut weights = vec![0u64; n];
+            let mut added = vec![false; n];
+            let mut heap = BinaryHeap::from([(0, active[0])]);
+            let (mut previous, mut last, mut cut) = (active[0], active[0], 0);
+            let mut count = 0;
+            while let Some((weight, i)) = heap.pop() {
+                if added[i] || weight != weights[i] {
+                    continue;
+                }
+
+                added[i] = true;
+                count += 1;
+                (previous, last, cut) = (last, i, weight);
+                for (&j, &w) in &adjacency[i] {
+                    if !added[j] {
+                        weights[j] += w;
+                        heap.push((weights[j], j));
+                    }
+                }
+            }
+
+            if count < active.len() {
+                // disconnected, the nodes reached are cut from the others for free
+                let side = active
+                    .iter()
+                    .filter(|&&i| added[i])
+                    .flat_map(|&i| groups[i].clone());
+                best = Some((0, side.collect()));
+                break;
+            }
+
+            if best.as_ref().is_none_or(|(weight, _)| cut < *weight) {
+                best = Some((cut, groups[last].clone()));
+            }
+
+            // merge the last node into the previous one
+            for (j, w) in std::mem::take(&mut adjacency[last]) {
+                adjacency[j].remove(&last);
+                if j != previous {
+                    *adjacency[previous].entry(j).or_default() += w;
+                    *adjacency[j].entry(previous).or_default() += w;
+                }
+            }
+            let merged = std::mem::take(&mut groups[last]);
+            groups[previous].extend(merged);
+            active.retain(|&i| i != last);
+        }
+
+        best.map(|(weight, side)| {
+            (
+                weight,
+                side.into_iter().map(|i| self.nodes[i].clone()).collect(),
+            )
+        })
+    }
+}
+
+/// Distance, in edges, from `start` to every node it reaches.
+pub fn bfs<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> HashMap<N, usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((node, distance)) = queue.pop_front() {
+        for next in neighbours(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    trace::count("bfs nodes expanded", distances.len() as u64);
+    distances
+}
+
+/// Nodes reached from `start`, in depth-first order.
+pub fn dfs<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> Vec<N>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::new();
+    let mut order = Vec::new();
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+        if !seen.insert(node.clone()) {
+            continue;
+        }
+
+        let next: Vec<N> = neighbours(&node).into_iter().collect();
+        // reversed, so that the first neighbour is visited first
+        stack.extend(next.into_iter().rev().filter(|n| !seen.contains(n)));
+        order.push(node);
+    }
+    trace::count("dfs nodes expanded", order.len() as u64);
+    order
+}
+
+/// Cheapest path from `start` to a node where `is_goal` holds, with its cost.
+pub fn dijkstra<N, I>(
+    start: N,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(u64, Vec<N>)>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    astar(start, neighbours, |_| 0, is_goal)
+}
+
+/// Cheapest path from `start` to a node where `is_goal` holds, with its cost,
+/// guided by `heuristic` that must never overestimate the remaining cost.
+pub fn astar<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> u64,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(u64, Vec<N>)>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    // nodes are numbered as they are found, so that they don't need to be Ord
+    let mut nodes = vec![start.clone()];
+    let mut indices = HashMap::from([(start.clone(), 0)]);
+    let mut costs = vec![0];
+    let mut parents = vec![usize::MAX];
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), 0, 0))]);
+
+    let mut expanded = 0;
+    while let Some(Reverse((_, cost, i))) = heap.pop() {
+        if cost > costs[i] {
+            continue;
+        }
+
+        expanded += 1;
+        if is_goal(&nodes[i]) {
+            trace::count("astar nodes expanded", expanded);
+            let mut path = vec![nodes[i].clone()];
+            let mut current = i;
+            while parents[current] != usize::MAX {
+                current = parents[current];
+                path.push(nodes[current].clone());
+            }
+            path.reverse();
+            return Some((cost, path));
+        }
+
+        for (next, weight) in neighbours(&nodes[i].clone()) {
+            let next_cost = cost + weight;
+            let j = match indices.get(&next) {
+                Some(&j) if costs[j] <= next_cost => continue,
+                Some(&j) => j,
+                None => {
+                    nodes.push(next.clone());
+                    costs.push(u64::MAX);
+                    parents.push(usize::MAX);
+                    indices.insert(next.clone(), nodes.len() - 1);
+                    nodes.len() - 1
+                }
+            };
+
+            costs[j] = next_cost;
+            parents[j] = i;
+            heap.push(Reverse((next_cost + heuristic(&next), next_cost, j)));
+        }
+    }
+    trace::count("astar nodes expanded", expanded);
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn grid_neighbours(&(x, y): &(i32, i32)) -> Vec<(i32, i32)> {
+        [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+            .into_iter()
+            .filter(|&(x, y)| (0..5).contains(&x) && (0..5).contains(&y) && (x, y) != (2, 1))
+            .collect()
+    }
+
+    #[test]
+    fn traversals() {
+        let distances = bfs((0, 0), grid_neighbours);
+        assert_eq!(distances.len(), 24);
+        assert_eq!(distances[&(4, 4)], 8);
+        assert_eq!(distances[&(2, 2)], 4);
+
+        let order = dfs((0, 0), grid_neighbours);
+        assert_eq!(order.len(), 24);
+        assert_eq!(order[..2], [(0, 0), (1, 0)]);
+    }
+
+    #[test]
+    fn shortest_paths() {
+        let weighted = |&p: &(i32, i32)| grid_neighbours(&p).into_iter().map(|n| (n, 1));
+        let (cost, path) = dijkstra((2, 0), weighted, |&p| p == (2, 2)).unwrap();
+        assert_eq!(cost, 4);
+        assert_eq!(path.len(), 5);
+
+        let manhattan = |&(x, y): &(i32, i32)| ((x - 2).abs() + (y - 2).abs()) as u64;
+        let (cost, _) = astar((2, 0), weighted, manhattan, |&p| p == (2, 2)).unwrap();
+        assert_eq!(cost, 4);
+
+        assert!(dijkstra((0, 0), weighted, |&p| p == (9, 9)).is_none());
+    }
+
+    #[test]
+    fn directed() {
+        let mut graph = Graph::new();
+        graph.add_edge("a", "b", 1);
+        graph.add_edge("a", "c", 5);
+        graph.add_edge("b", "c", 1);
+        graph.add_edge("c", "d", 2);
+        assert_eq!(graph.topological_order(), Some(vec!["a", "b", "c", "d"]));
+        assert_eq!(graph.find_cycle(), None);
+        assert_eq!(graph.longest_paths(&"a").unwrap()[&"d"], 7);
+
+        graph.add_edge("d", "b", 1);
+        assert_eq!(graph.topological_order(), None);
+        assert_eq!(graph.find_cycle(), Some(vec!["b", "c", "d"]));
+    }
+
+    #[test]
+    fn undirected() {
+        let mut graph = Graph::new();
+        for (a, b) in [(1, 2), (2, 3), (3, 1), (4, 5), (5, 6), (6, 4), (3, 4)] {
+            graph.add_undirected_edge(a, b, 1);
+        }
+        assert_eq!(graph.connected_components().len(), 1);
+
+        let (weight, mut side) = graph.min_cut().unwrap();
+        side.sort();
+        assert_eq!(weight, 1);
+        assert!(side == [1, 2, 3] || side == [4, 5, 6]);
+
+        graph.add_node(7);
+        assert_eq!(graph.connected_components().len(), 2);
+        assert_eq!(graph.min_cut().unwrap().0, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "edges of different weights in both directions")]
+    fn asymmetric_cut() {
+        let mut graph = Graph::new();
+        graph.add_edge(1, 2, 3);
+        graph.add_edge(2, 1, 1);
+        graph.min_cut();
+    }
+}
@@ -0,0 +1,315 @@
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+use crate::utils::parse_error::ParseError;
+
+/// Position in a grid, as `(y, x)`, that is row and column.
+pub type Position = (usize, usize);
+
+const NEIGHBOURS_4: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+const NEIGHBOURS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Rectangular grid of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Grid of `width` by `height` cells, all set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Grid from its rows, `None` if they don't all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first().map(Vec::len).unwrap_or_default();
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+
+        Some(Grid {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parses a grid with a cell for each character, as mapped by `cell`.
+    ///
+    /// Characters mapped to `None` and lines of different lengths are errors.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> Option<T>) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for (y, line) in input.lines().enumerate() {
+            let mut length = 0;
+            for (x, c) in line.chars().enumerate() {
+                let value = cell(c).ok_or_else(|| {
+                    ParseError::new(input, y, x, format!("unexpected character '{}'", c))
+                })?;
+                cells.push(value);
+                length += 1;
+            }
+
+            let width = *width.get_or_insert(length);
+            if length != width {
+                let message = format!("expected {} cells, found {}", width, length);
+                return Err(ParseError::new(input, y, length.min(width), message));
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            width: width.unwrap_or_default(),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, (y, x): Position) -> bool {
+        y < self.height && x < self.width
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.in_bounds(position)
+            .then(|| &self.cells[position.0 * self.width + position.1])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        if self.in_bounds(position) {
+            Some(&mut self.cells[position.0 * self.width + position.1])
+        } else {
+            None
+        }
+    }
+
+    /// Moves `position` by `(dy, dx)`, `None` if that leaves the grid.
+    pub fn offset(&self, (y, x): Position, (dy, dx): (isize, isize)) -> Option<Position> {
+        let position = (y.checked_add_signed(dy)?, x.checked_add_signed(dx)?);
+        self.in_bounds(position).then_some(position)
+    }
+
+    /// Orthogonal neighbours of `position` inside the grid.
+    pub fn neighbours4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        NEIGHBOURS_4
+            .iter()
+            .filter_map(move |&delta| self.offset(position, delta))
+    }
+
+    /// Orthogonal and diagonal neighbours of `position` inside the grid.
+    pub fn neighbours8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        NEIGHBOURS_8
+            .iter()
+            .filter_map(move |&delta| self.offset(position, delta))
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (y, x)))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks_exact panics on 0, a grid without columns has no cells anyway
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} out of the grid", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Grid of the same size with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Grid with rows and columns swapped.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells: self.columns().flatten().cloned().collect(),
+        }
+    }
+
+    /// Grid rotated by a quarter turn clockwise.
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let mut transposed = self.transpose();
+        transposed.flip_horizontal();
+        transposed
+    }
+
+    /// Grid rotated by a quarter turn counterclockwise.
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let mut transposed = self.transpose();
+        transposed.flip_vertical();
+        transposed
+    }
+
+    /// Mirrors the grid left to right.
+    pub fn flip_horizontal(&mut self) {
+        for row in self.cells.chunks_exact_mut(self.width.max(1)) {
+            row.reverse();
+        }
+    }
+
+    /// Mirrors the grid top to bottom.
+    pub fn flip_vertical(&mut self) {
+        for y in 0..self.height / 2 {
+            for x in 0..self.width {
+                self.cells
+                    .swap(y * self.width + x, (self.height - 1 - y) * self.width + x);
+            }
+        }
+    }
+}
+
+impl<T> Default for Grid<T> {
+    fn default() -> Self {
+        Grid {
+            width: 0,
+            height: 0,
+            cells: Vec::new(),
+        }
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (y, x): Position) -> &T {
+        assert!(self.in_bounds((y, x)), "position {:?} out of the grid", (y, x));
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, (y, x): Position) -> &mut T {
+        assert!(self.in_bounds((y, x)), "position {:?} out of the grid", (y, x));
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn chars(input: &str) -> Grid<char> {
+        Grid::parse(input, Some).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let grid = chars("ab\ncd\nef\n");
+        assert_eq!((grid.width(), grid.height()), (2, 3));
+        assert_eq!(grid[(2, 1)], 'f');
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.to_string(), "ab\ncd\nef");
+
+        let error = Grid::parse("..\n.x", |c| (c == '.').then_some(())).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        let error = Grid::parse("..\n.", Some).unwrap_err();
+        assert_eq!(error.message, "expected 2 cells, found 1");
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = chars("abc\ndef\nghi");
+        let corner: Vec<Position> = grid.neighbours4((0, 0)).collect();
+        assert_eq!(corner, vec![(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbours8((0, 0)).count(), 3);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        assert_eq!(grid.offset((2, 2), (0, 1)), None);
+    }
+
+    #[test]
+    fn views() {
+        let grid = chars("abc\ndef");
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.columns().count(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 out of the grid")]
+    fn column_out_of_bounds() {
+        let _ = chars("abc\ndef").column(3);
+    }
+
+    #[test]
+    fn transformations() {
+        let grid = chars("abc\ndef");
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+    }
+}
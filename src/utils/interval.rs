@@ -0,0 +1,290 @@
+use std::ops::{Add, Sub};
+
+/// Half-open interval of the values from `start` included to `end` excluded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Copy + Ord> Interval<T> {
+    pub const fn new(start: T, end: T) -> Self {
+        Interval { start, end }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Values in both intervals, `None` if there are none.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let result = Interval::new(self.start.max(other.start), self.end.min(other.end));
+        (!result.is_empty()).then_some(result)
+    }
+
+    /// Splits the interval into the values below `value` and the others.
+    pub fn split_at(&self, value: T) -> (Option<Self>, Option<Self>) {
+        let below = Interval::new(self.start, self.end.min(value));
+        let above = Interval::new(self.start.max(value), self.end);
+        (
+            (!below.is_empty()).then_some(below),
+            (!above.is_empty()).then_some(above),
+        )
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T>> Interval<T> {
+    /// Number of values in the interval, for integers.
+    pub fn len(&self) -> T {
+        self.end.max(self.start) - self.start
+    }
+}
+
+/// Set of values stored as sorted, disjoint and non adjacent intervals.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet {
+            intervals: Vec::new(),
+        }
+    }
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Interval<T>> {
+        self.intervals.iter()
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.intervals.iter().any(|i| i.contains(value))
+    }
+
+    /// Smallest value of the set.
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|i| i.start)
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+
+        // intervals overlapping or touching the new one are merged into it
+        let first = self.intervals.partition_point(|i| i.end < interval.start);
+        let last = self.intervals.partition_point(|i| i.start <= interval.end);
+        let merged = self.intervals[first..last].iter().fold(interval, |a, b| {
+            Interval::new(a.start.min(b.start), a.end.max(b.end))
+        });
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for &interval in &other.intervals {
+            result.insert(interval);
+        }
+        result
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a, b) = (self.intervals[i], other.intervals[j]);
+            if let Some(common) = a.intersection(&b) {
+                intervals.push(common);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { intervals }
+    }
+
+    /// Values of this set that aren't in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        for &interval in &self.intervals {
+            let mut rest = Some(interval);
+            for removed in other.intervals.iter().filter(|r| r.overlaps(&interval)) {
+                let Some(current) = rest else { break };
+                let (below, _) = current.split_at(removed.start);
+                let (_, above) = current.split_at(removed.end);
+                intervals.extend(below);
+                rest = above;
+            }
+            intervals.extend(rest);
+        }
+        IntervalSet { intervals }
+    }
+}
+
+impl<T: Copy + Ord + Default + Add<Output = T> + Sub<Output = T>> IntervalSet<T> {
+    /// Number of values in the set, for integers.
+    pub fn len(&self) -> T {
+        self.intervals
+            .iter()
+            .map(Interval::len)
+            .fold(T::default(), |a, b| a + b)
+    }
+}
+
+impl<T: Copy + Ord> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        let mut set = IntervalSet::new();
+        set.insert(interval);
+        set
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        for interval in iter {
+            set.insert(interval);
+        }
+        set
+    }
+}
+
+/// Function moving the values of some intervals by an offset each, and
+/// leaving the other values unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PiecewiseMap<T> {
+    /// intervals of values, and where their start is moved
+    rules: Vec<(Interval<T>, T)>,
+}
+
+impl<T> Default for PiecewiseMap<T> {
+    fn default() -> Self {
+        PiecewiseMap { rules: Vec::new() }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> PiecewiseMap<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Moves the values of `source` so that its start becomes `destination`.
+    ///
+    /// Rules added first win where they overlap.
+    pub fn add(&mut self, source: Interval<T>, destination: T) {
+        self.rules.push((source, destination));
+    }
+
+    fn shift(value: T, source: &Interval<T>, destination: T) -> T {
+        // subtracted first, so that it also works for unsigned types
+        value - source.start + destination
+    }
+
+    pub fn map(&self, value: T) -> T {
+        self.rules
+            .iter()
+            .find(|(source, _)| source.contains(value))
+            .map(|(source, destination)| Self::shift(value, source, *destination))
+            .unwrap_or(value)
+    }
+
+    /// Image of every value of `set`.
+    pub fn map_set(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut rest = set.clone();
+        let mut result = IntervalSet::new();
+        for (source, destination) in &self.rules {
+            let source_set = IntervalSet::from(*source);
+            for moved in rest.intersection(&source_set).iter() {
+                result.insert(Interval::new(
+                    Self::shift(moved.start, source, *destination),
+                    Self::shift(moved.end, source, *destination),
+                ));
+            }
+            rest = rest.difference(&source_set);
+        }
+        result.union(&rest)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn set(intervals: &[(i32, i32)]) -> IntervalSet<i32> {
+        intervals
+            .iter()
+            .map(|&(a, b)| Interval::new(a, b))
+            .collect()
+    }
+
+    #[test]
+    fn intervals() {
+        let a = Interval::new(0, 10);
+        assert!(a.contains(0) && !a.contains(10));
+        assert_eq!(
+            a.intersection(&Interval::new(5, 15)),
+            Some(Interval::new(5, 10))
+        );
+        assert_eq!(a.intersection(&Interval::new(10, 15)), None);
+        assert_eq!(
+            a.split_at(3),
+            (Some(Interval::new(0, 3)), Some(Interval::new(3, 10)))
+        );
+        assert_eq!(a.split_at(10), (Some(a), None));
+        assert_eq!(Interval::new(5u32, 2).len(), 0);
+    }
+
+    #[test]
+    fn sets() {
+        let a = set(&[(0, 5), (10, 15), (5, 7)]);
+        assert_eq!(a, set(&[(0, 7), (10, 15)]));
+        assert_eq!(a.len(), 12);
+
+        let b = set(&[(3, 12), (14, 20)]);
+        assert_eq!(a.union(&b), set(&[(0, 20)]));
+        assert_eq!(a.intersection(&b), set(&[(3, 7), (10, 12), (14, 15)]));
+        assert_eq!(a.difference(&b), set(&[(0, 3), (12, 14)]));
+        assert_eq!(b.difference(&a), set(&[(7, 10), (15, 20)]));
+        assert!(a.difference(&a).is_empty());
+    }
+
+    #[test]
+    fn piecewise() {
+        let mut map = PiecewiseMap::new();
+        map.add(Interval::new(98u64, 100), 50);
+        map.add(Interval::new(50, 98), 52);
+        assert_eq!(map.map(98), 50);
+        assert_eq!(map.map(53), 55);
+        assert_eq!(map.map(10), 10);
+
+        let seeds = IntervalSet::from(Interval::new(90, 110));
+        let expected: IntervalSet<u64> = [
+            Interval::new(92, 100),
+            Interval::new(50, 52),
+            Interval::new(100, 110),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(map.map_set(&seeds), expected);
+    }
+}
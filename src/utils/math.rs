@@ -0,0 +1,226 @@
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+/// Primitive integer types, for the generic functions of this module.
+pub trait Integer:
+    Copy
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+}
+
+/// Signed primitive integer types.
+pub trait Signed: Integer + Neg<Output = Self> {}
+
+macro_rules! integer {
+    ($($t:ty),*) => {
+        $(impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+        })*
+    };
+}
+
+macro_rules! signed {
+    ($($t:ty),*) => {
+        $(impl Signed for $t {})*
+    };
+}
+
+integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+signed!(i8, i16, i32, i64, i128, isize);
+
+fn abs<T: Integer>(n: T) -> T {
+    if n < T::ZERO {
+        T::ZERO - n
+    } else {
+        n
+    }
+}
+
+/// Greatest common divisor, always positive, `gcd(0, 0)` is 0.
+pub fn gcd<T: Integer>(mut a: T, mut b: T) -> T {
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    abs(a)
+}
+
+/// Least common multiple, 0 if either number is 0.
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    if a == T::ZERO || b == T::ZERO {
+        T::ZERO
+    } else {
+        abs(a / gcd(a, b) * b)
+    }
+}
+
+/// Least common multiple of every number, 1 for none.
+pub fn lcm_all<T: Integer>(numbers: impl IntoIterator<Item = T>) -> T {
+    numbers.into_iter().fold(T::ONE, lcm)
+}
+
+/// Returns `(g, x, y)` with `g = gcd(a, b) = a * x + b * y`.
+pub fn extended_gcd<T: Signed>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::ONE, T::ZERO);
+    let (mut old_y, mut y) = (T::ZERO, T::ONE);
+    while r != T::ZERO {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < T::ZERO {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Solves the system `x = r (mod m)` for every `(r, m)`, returning the
+/// smallest non negative solution and the modulus of all the solutions.
+///
+/// The moduli don't need to be coprime. `None` if the system has no solution,
+/// a modulus isn't positive or the modulus doesn't fit an `i64`.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    let (mut x, mut m) = (0i128, 1i128);
+    for (r, n) in congruences {
+        if n <= 0 {
+            return None;
+        }
+        let (r, n) = (r as i128, n as i128);
+        let (g, p, _) = extended_gcd(m, n);
+        if (r - x) % g != 0 {
+            return None;
+        }
+
+        let n_g = n / g;
+        let k = ((r - x) / g % n_g * p % n_g + n_g) % n_g;
+        x += m * k;
+        m *= n_g;
+        x = x.rem_euclid(m);
+        if m > i64::MAX as i128 {
+            return None;
+        }
+    }
+
+    Some((x as i64, m as i64))
+}
+
+/// `base` to the power `exp`, modulo `modulus`, which must not be 0.
+pub fn mod_pow(base: u64, mut exp: u64, modulus: u64) -> u64 {
+    assert!(modulus != 0, "modulus 0");
+    if modulus == 1 {
+        return 0;
+    }
+
+    let modulus = modulus as u128;
+    let (mut base, mut result) = (base as u128 % modulus, 1u128);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    result as u64
+}
+
+/// Inverse of `a` modulo `modulus`, `None` if they aren't coprime or the
+/// modulus isn't positive.
+pub fn mod_inv(a: i64, modulus: i64) -> Option<i64> {
+    if modulus <= 0 {
+        return None;
+    }
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// Largest integer whose square is at most `n`.
+pub fn isqrt(n: u64) -> u64 {
+    if n < 2 {
+        return n;
+    }
+
+    // Newton's method from above, starting with an estimate that can't be too low
+    let mut x = (n as f64).sqrt() as u64 + 1;
+    loop {
+        let next = (x + n / x) / 2;
+        if next >= x {
+            break;
+        }
+        x = next;
+    }
+    while x as u128 * x as u128 > n as u128 {
+        x -= 1;
+    }
+    x
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn gcd_lcm() {
+        assert_eq!(gcd(12u32, 18), 6);
+        assert_eq!(gcd(-12i64, 18), 6);
+        assert_eq!(gcd(0u8, 0), 0);
+        assert_eq!(lcm(4usize, 6), 12);
+        assert_eq!(lcm(0i32, 6), 0);
+        assert_eq!(lcm_all([2u64, 3, 4, 5]), 60);
+        assert_eq!(lcm_all(Vec::<u64>::new()), 1);
+    }
+
+    #[test]
+    fn extended() {
+        for (a, b) in [(240i64, 46), (-7, 3), (0, 5), (17, 0)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+
+    #[test]
+    fn chinese_remainder() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([]), Some((0, 1)));
+        assert_eq!(crt([(2, 3), (0, 0)]), None);
+        assert_eq!(crt([(1, -4)]), None);
+    }
+
+    #[test]
+    fn modular() {
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(7, 0, 13), 1);
+        assert_eq!(mod_pow(u64::MAX, 2, u64::MAX - 1), 1);
+        assert_eq!(mod_inv(3, 11), Some(4));
+        assert_eq!(mod_inv(-3, 11), Some(7));
+        assert_eq!(mod_inv(4, 8), None);
+        assert_eq!(mod_inv(3, 0), None);
+        assert_eq!(mod_inv(3, -11), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "modulus 0")]
+    fn mod_pow_zero_modulus() {
+        mod_pow(2, 10, 0);
+    }
+
+    #[test]
+    fn square_root() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(15), 3);
+        assert_eq!(isqrt(16), 4);
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+    }
+}
@@ -1 +1,10 @@
-pub mod tokenizer;
\ No newline at end of file
+pub mod tokenizer;
+pub mod parse_error;
+pub mod grid;
+pub mod geom;
+pub mod math;
+pub mod graph;
+pub mod interval;
+pub mod parse;
+pub mod grammar;
+pub mod bigint;
@@ -0,0 +1,121 @@
+use std::str::FromStr;
+
+use crate::utils::grid::Grid;
+use crate::utils::parse_error::ParseError;
+
+/// Every integer of `line`, a `-` right before the digits being its sign.
+pub fn ints<T: FromStr>(line: &str) -> Result<Vec<T>, ParseError> {
+    let bytes = line.as_bytes();
+    let mut result = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let negative = i > 0 && bytes[i - 1] == b'-' && (i < 2 || !bytes[i - 2].is_ascii_digit());
+        let start = if negative { i - 1 } else { i };
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+
+        let token = &line[start..i];
+        let value = token
+            .parse()
+            .map_err(|_| ParseError::at_token(line, token, format!("invalid number {}", token)))?;
+        result.push(value);
+    }
+    Ok(result)
+}
+
+/// Sections of `input` separated by blank lines, without their last newline.
+pub fn blocks(input: &str) -> Vec<&str> {
+    let mut blocks = Vec::new();
+    let mut start = None;
+    let mut end = 0;
+    let mut offset = 0;
+    for line in input.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            if let Some(start) = start.take() {
+                blocks.push(&input[start..end]);
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + line.trim_end_matches(['\r', '\n']).len();
+        }
+        offset += line.len();
+    }
+    if let Some(start) = start {
+        blocks.push(&input[start..end]);
+    }
+    blocks
+}
+
+/// Grid of the characters of `input`.
+pub fn grid(input: &str) -> Result<Grid<char>, ParseError> {
+    Grid::parse(input, Some)
+}
+
+/// Parses every line with `f`, the first error pointing at its line.
+///
+/// A [`ParseError`] from `f` keeps its column, any other error is reported at
+/// the start of the line.
+pub fn lines_with<T, E>(
+    input: &str,
+    mut f: impl FnMut(&str) -> Result<T, E>,
+) -> Result<Vec<T>, ParseError>
+where
+    E: Into<anyhow::Error>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            f(line).map_err(|e| match e.into().downcast::<ParseError>() {
+                Ok(error) => error.on_line(input, i),
+                Err(error) => ParseError::new(input, i, 0, error.to_string()),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn integers() {
+        assert_eq!(ints::<i32>("p=0,4 v=3,-3"), Ok(vec![0, 4, 3, -3]));
+        assert_eq!(ints::<i64>("1-2 -5"), Ok(vec![1, 2, -5]));
+        assert_eq!(ints::<u8>("none"), Ok(vec![]));
+
+        let error = ints::<u8>("1 300").unwrap_err();
+        assert_eq!(
+            (error.column, error.message.as_str()),
+            (2, "invalid number 300")
+        );
+    }
+
+    #[test]
+    fn sections() {
+        assert_eq!(blocks("a\nb\n\n\nc\n"), vec!["a\nb", "c"]);
+        assert_eq!(blocks("\r\na\r\n\r\nb"), vec!["a", "b"]);
+        assert!(blocks("\n\n").is_empty());
+    }
+
+    #[test]
+    fn lines() {
+        let input = "1 2\n3 x 400\n";
+        assert_eq!(
+            lines_with(input, ints::<u32>),
+            Ok(vec![vec![1, 2], vec![3, 400]])
+        );
+
+        let error = lines_with(input, ints::<u8>).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 4));
+
+        let error = lines_with(input, |line| line.parse::<u32>()).unwrap_err();
+        assert_eq!((error.line, error.column), (0, 0));
+    }
+}
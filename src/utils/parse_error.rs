@@ -0,0 +1,131 @@
+use std::fmt::{Debug, Display};
+
+use pest::error::{ErrorVariant, LineColLocation};
+use pest::RuleType;
+
+/// Error found while parsing an input, pointing at the offending position.
+///
+/// Lines and columns are 0-based, they are shown 1-based.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+    source_line: String,
+}
+
+impl ParseError {
+    /// Error at `column` of the line `line` of `input`.
+    pub fn new(input: &str, line: usize, column: usize, message: impl Into<String>) -> Self {
+        ParseError {
+            line,
+            column,
+            message: message.into(),
+            source_line: input.lines().nth(line).unwrap_or_default().into(),
+        }
+    }
+
+    /// Error for a missing or invalid `what` at `column` of the line `line`.
+    pub fn expected(input: &str, line: usize, column: usize, what: impl Display) -> Self {
+        Self::new(input, line, column, format!("expected {}", what))
+    }
+
+    /// Error at the byte `offset` of `input`.
+    pub fn at_offset(input: &str, offset: usize, message: impl Into<String>) -> Self {
+        let before = &input[..offset.min(input.len())];
+        let line = before.matches('\n').count();
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let column = before[line_start..].chars().count();
+        Self::new(input, line, column, message)
+    }
+
+    /// Same error, for a line parsed on its own that is the line `line` of `input`.
+    pub fn on_line(self, input: &str, line: usize) -> Self {
+        Self::new(input, line, self.column, self.message)
+    }
+
+    /// Error at the start of `token`, which must be a slice of `input`.
+    pub fn at_token(input: &str, token: &str, message: impl Into<String>) -> Self {
+        let offset = (token.as_ptr() as usize).saturating_sub(input.as_ptr() as usize);
+        Self::at_offset(input, offset, message)
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let line_number = (self.line + 1).to_string();
+        let padding = " ".repeat(line_number.len());
+
+        writeln!(
+            f,
+            "{} at line {}, column {}",
+            self.message,
+            self.line + 1,
+            self.column + 1
+        )?;
+        writeln!(f, "{} | {}", line_number, self.source_line)?;
+        write!(f, "{} | {}^", padding, " ".repeat(self.column))
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl<R: RuleType> From<pest::error::Error<R>> for ParseError {
+    fn from(error: pest::error::Error<R>) -> Self {
+        let (line, column) = match error.line_col {
+            LineColLocation::Pos(position) | LineColLocation::Span(position, _) => position,
+        };
+
+        let rules = |rules: &[R]| {
+            rules
+                .iter()
+                .map(|r| format!("{:?}", r))
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+        let message = match &error.variant {
+            ErrorVariant::ParsingError { positives, .. } if !positives.is_empty() => {
+                format!("expected {}", rules(positives))
+            }
+            ErrorVariant::ParsingError { negatives, .. } => {
+                format!("unexpected {}", rules(negatives))
+            }
+            ErrorVariant::CustomError { message } => message.clone(),
+        };
+
+        ParseError {
+            line: line - 1,
+            column: column - 1,
+            message,
+            // pest shows the line breaks of spans ending a line as symbols
+            source_line: error
+                .line()
+                .trim_end_matches(['\r', '\n', '␍', '␊'])
+                .into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn render() {
+        let error = ParseError::expected("abc\nde f\n", 1, 3, "number");
+        assert_eq!(
+            error.to_string(),
+            "expected number at line 2, column 4\n2 | de f\n  |    ^"
+        );
+    }
+
+    #[test]
+    fn offset() {
+        let input = "abc\nde f\n";
+        let error = ParseError::at_token(input, &input[7..], "invalid");
+        assert_eq!((error.line, error.column), (1, 3));
+
+        let error = ParseError::at_offset(input, 0, "invalid");
+        assert_eq!((error.line, error.column), (0, 0));
+    }
+}
@@ -1,77 +1,156 @@
-pub trait Tokenizer {
+use std::str::FromStr;
+
+use crate::utils::parse_error::ParseError;
+
+/// Scanner over a text, returning slices of it instead of copies.
+pub trait Tokenizer<'a> {
+    /// Byte offset of the next character.
+    fn position(&self) -> usize;
+    /// Line of the next character, 0-based.
+    fn line(&self) -> usize;
+    /// Column of the next character, in characters, 0-based.
+    fn column(&self) -> usize;
+    fn is_at_end(&self) -> bool;
     fn peek_char(&self) -> Option<char>;
     fn next_char(&mut self) -> Option<char>;
-    fn read_until(&mut self, until: char) -> String;
-    fn next_digit(&mut self) -> String;
+    /// Text up to `until` or the end of input, skipping `until` itself.
+    fn read_until(&mut self, until: char) -> &'a str;
+    /// Optionally signed integer, after skipping spaces but not newlines.
+    fn next_int<T: FromStr>(&mut self) -> Result<T, ParseError>;
+    /// Consumes `literal`, failing if the input doesn't continue with it.
+    fn expect(&mut self, literal: &str) -> Result<(), ParseError>;
+    /// Skips spaces and tabs.
+    fn skip_spaces(&mut self);
+    /// Skips every whitespace, newlines included.
     fn skip_whitespace(&mut self);
+    /// Error with `message` at the current position.
+    fn error(&self, message: impl Into<String>) -> ParseError;
 }
 
-pub struct StringTokenizer {
+pub struct StringTokenizer<'a> {
+    input: &'a str,
     position: usize,
-    chars: Vec<char>,
+    line: usize,
+    line_start: usize,
 }
 
-impl From<&str> for StringTokenizer {
-    fn from(string: &str) -> Self {
+impl<'a> From<&'a str> for StringTokenizer<'a> {
+    fn from(input: &'a str) -> Self {
         StringTokenizer {
+            input,
             position: 0,
-            chars: string.chars().collect(),
+            line: 0,
+            line_start: 0,
         }
     }
 }
 
-impl Tokenizer for StringTokenizer {
-    fn peek_char(&self) -> Option<char> {
-        if self.position < self.chars.len() {
-            Some(self.chars[self.position])
-        } else {
-            None
+impl<'a> StringTokenizer<'a> {
+    fn rest(&self) -> &'a [u8] {
+        &self.input.as_bytes()[self.position..]
+    }
+
+    /// Moves forward by `length` bytes, returning the text skipped.
+    fn advance(&mut self, length: usize) -> &'a str {
+        let skipped = &self.input[self.position..self.position + length];
+        for (i, byte) in skipped.bytes().enumerate() {
+            if byte == b'\n' {
+                self.line += 1;
+                self.line_start = self.position + i + 1;
+            }
         }
+        self.position += length;
+        skipped
+    }
+
+    fn skip_while(&mut self, predicate: impl Fn(u8) -> bool) -> &'a str {
+        let length = self.rest().iter().take_while(|&&b| predicate(b)).count();
+        self.advance(length)
+    }
+}
+
+impl<'a> Tokenizer<'a> for StringTokenizer<'a> {
+    fn position(&self) -> usize {
+        self.position
+    }
+
+    fn line(&self) -> usize {
+        self.line
+    }
+
+    fn column(&self) -> usize {
+        self.input[self.line_start..self.position].chars().count()
+    }
+
+    fn is_at_end(&self) -> bool {
+        self.position >= self.input.len()
+    }
+
+    fn peek_char(&self) -> Option<char> {
+        self.input[self.position..].chars().next()
     }
 
     fn next_char(&mut self) -> Option<char> {
-        if self.position < self.chars.len() {
-            let result = self.chars[self.position];
-            self.position += 1;
-            Some(result)
-        } else {
-            None
-        }
+        let c = self.peek_char()?;
+        self.advance(c.len_utf8());
+        Some(c)
     }
 
-    fn read_until(&mut self, until: char) -> String {
-        let mut result = String::new();
-        loop {
-            match self.next_char() {
-                None => break result,
-                Some(ch) => {
-                    if until == ch {
-                        break result
-                    } 
-                    result.push(ch);
-                }
+    fn read_until(&mut self, until: char) -> &'a str {
+        let rest = &self.input[self.position..];
+        match rest.find(until) {
+            Some(length) => {
+                let result = self.advance(length);
+                self.advance(until.len_utf8());
+                result
             }
+            None => self.advance(rest.len()),
         }
     }
 
-    fn next_digit(&mut self) -> String {
-        let mut result = String::new();
-        
-        while self.peek_char().is_some_and(|c| !c.is_ascii_digit()) {
-            self.next_char();
+    fn next_int<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        self.skip_spaces();
+
+        let start = self.position;
+        let sign = match self.rest().first() {
+            Some(b'-' | b'+') => 1,
+            _ => 0,
+        };
+        let digits = self.rest()[sign..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        if digits == 0 {
+            return Err(self.error("expected number"));
         }
 
-        while self.peek_char().is_some_and(|c| c.is_ascii_digit()) {
-            result.push(self.next_char().unwrap());
+        let number = &self.input[start..start + sign + digits];
+        let value = number
+            .parse()
+            .map_err(|_| self.error(format!("invalid number {}", number)))?;
+        self.advance(sign + digits);
+        Ok(value)
+    }
+
+    fn expect(&mut self, literal: &str) -> Result<(), ParseError> {
+        if self.rest().starts_with(literal.as_bytes()) {
+            self.advance(literal.len());
+            Ok(())
+        } else {
+            Err(self.error(format!("expected \"{}\"", literal)))
         }
+    }
 
-        result
+    fn skip_spaces(&mut self) {
+        self.skip_while(|b| b == b' ' || b == b'\t');
     }
 
     fn skip_whitespace(&mut self) {
-        while self.peek_char().is_some_and(|c| c.is_ascii_whitespace()) {
-            self.next_char();
-        }
+        self.skip_while(|b| b.is_ascii_whitespace());
+    }
+
+    fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::new(self.input, self.line, self.column(), message)
     }
 }
 
@@ -99,10 +178,33 @@ mod test {
     }
 
     #[test]
-    fn read_digit() {
-        let mut tokenizer: StringTokenizer = StringTokenizer::from("aabbc123 67");
-        assert_eq!(tokenizer.next_digit(), "123");
+    fn read_int() {
+        let mut tokenizer: StringTokenizer = StringTokenizer::from("123 -67+4 x");
+        assert_eq!(tokenizer.next_int::<u32>(), Ok(123));
         assert_eq!(tokenizer.peek_char(), Some(' '));
-        assert_eq!(tokenizer.next_digit(), "67");
+        assert_eq!(tokenizer.next_int::<i32>(), Ok(-67));
+        assert_eq!(tokenizer.next_int::<i64>(), Ok(4));
+
+        let error = tokenizer.next_int::<u32>().unwrap_err();
+        assert_eq!((error.column, error.message.as_str()), (10, "expected number"));
+
+        let mut tokenizer: StringTokenizer = StringTokenizer::from("-1");
+        assert!(tokenizer.next_int::<u32>().is_err());
+        assert_eq!(tokenizer.position(), 0);
+    }
+
+    #[test]
+    fn expect() {
+        let mut tokenizer: StringTokenizer = StringTokenizer::from("Card 1:\nCard é");
+        assert_eq!(tokenizer.expect("Card"), Ok(()));
+        assert_eq!(tokenizer.next_int::<u32>(), Ok(1));
+        assert_eq!(tokenizer.expect(":"), Ok(()));
+        tokenizer.skip_whitespace();
+        assert_eq!(tokenizer.expect("Card "), Ok(()));
+
+        let error = tokenizer.expect("x").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 5));
+        assert_eq!(tokenizer.next_char(), Some('é'));
+        assert!(tokenizer.is_at_end());
     }
 }
@@ -0,0 +1,347 @@
+//! Rendering of puzzle states, for the days implementing [`Visualize`], as
+//! coloured terminal output or as images.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+use colored::Colorize;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+
+use crate::utils::grid::Grid;
+
+/// Side of a cell in images, in pixels.
+const SCALE: usize = 4;
+
+/// Time each frame of a GIF is shown, in hundredths of a second.
+const GIF_DELAY: u16 = 10;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+/// Cell of a frame: shown as its glyph on the terminal, as a square of its
+/// colour in images.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub glyph: char,
+    pub color: Rgb,
+}
+
+impl Cell {
+    pub fn new(glyph: char, color: Rgb) -> Self {
+        Cell { glyph, color }
+    }
+}
+
+pub type Frame = Grid<Cell>;
+
+/// Implemented by the days that can show how they solve a part.
+pub trait Visualize {
+    /// Successive states of the puzzle while solving `part`.
+    fn frames(&self, part: u32) -> Vec<Frame>;
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameFormat {
+    /// one PPM image per frame
+    Ppm,
+    /// one PNG image per frame
+    Png,
+    /// a single animated GIF
+    Gif,
+}
+
+pub fn render_terminal(frames: &[Frame], out: &mut dyn Write) -> io::Result<()> {
+    for (i, frame) in frames.iter().enumerate() {
+        if frames.len() > 1 {
+            writeln!(out, "frame {}/{}", i + 1, frames.len())?;
+        }
+        for row in frame.rows() {
+            for cell in row {
+                let Rgb(r, g, b) = cell.color;
+                write!(out, "{}", cell.glyph.to_string().truecolor(r, g, b))?;
+            }
+            writeln!(out)?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes `frames` in `dir` as files named after `name`, returning their paths.
+pub fn save(
+    dir: &Path,
+    name: &str,
+    frames: &[Frame],
+    format: FrameFormat,
+) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)?;
+
+    if format == FrameFormat::Gif {
+        let path = dir.join(format!("{}.gif", name));
+        fs::write(&path, gif(frames))?;
+        return Ok(vec![path]);
+    }
+
+    let mut paths = Vec::new();
+    for (i, frame) in frames.iter().enumerate() {
+        let (extension, content) = match format {
+            FrameFormat::Ppm => ("ppm", ppm(frame)),
+            _ => ("png", png(frame)?),
+        };
+        let path = dir.join(format!("{}-{:04}.{}", name, i, extension));
+        fs::write(&path, content)?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+/// Pixels of an image of `width` by `height`, row by row, with each value of
+/// `grid` scaled to a square and the default value past its edges.
+fn scale<T: Copy + Default>(grid: &Grid<T>, width: usize, height: usize) -> Vec<T> {
+    let mut pixels = Vec::with_capacity(width * height);
+    for y in 0..height {
+        for x in 0..width {
+            pixels.push(
+                grid.get((y / SCALE, x / SCALE))
+                    .copied()
+                    .unwrap_or_default(),
+            );
+        }
+    }
+    pixels
+}
+
+fn pixels(frame: &Frame, width: usize, height: usize) -> Vec<Rgb> {
+    scale(&frame.map(|c| c.color), width, height)
+}
+
+fn size(frame: &Frame) -> (usize, usize) {
+    (frame.width() * SCALE, frame.height() * SCALE)
+}
+
+fn ppm(frame: &Frame) -> Vec<u8> {
+    let (width, height) = size(frame);
+    let mut content = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+    for Rgb(r, g, b) in pixels(frame, width, height) {
+        content.extend([r, g, b]);
+    }
+    content
+}
+
+fn png(frame: &Frame) -> io::Result<Vec<u8>> {
+    fn chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+        png.extend((data.len() as u32).to_be_bytes());
+        let start = png.len();
+        png.extend(kind);
+        png.extend(data);
+        let crc = crc32fast::hash(&png[start..]);
+        png.extend(crc.to_be_bytes());
+    }
+
+    let (width, height) = size(frame);
+    let mut header = Vec::new();
+    header.extend((width as u32).to_be_bytes());
+    header.extend((height as u32).to_be_bytes());
+    // 8 bits RGB, default compression, filtering and no interlacing
+    header.extend([8, 2, 0, 0, 0]);
+
+    // each row starts with its filter type, none
+    let mut data = Vec::with_capacity((width * 3 + 1) * height);
+    for row in pixels(frame, width, height).chunks(width.max(1)) {
+        data.push(0);
+        for &Rgb(r, g, b) in row {
+            data.extend([r, g, b]);
+        }
+    }
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&data)?;
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    chunk(&mut png, b"IHDR", &header);
+    chunk(&mut png, b"IDAT", &encoder.finish()?);
+    chunk(&mut png, b"IEND", &[]);
+    Ok(png)
+}
+
+/// Palette of every colour used, reduced to 3-3-2 bits colours if there
+/// are more than a GIF can hold.
+fn palette(frames: &[Frame]) -> (Vec<Rgb>, HashMap<Rgb, u8>) {
+    // black, sorted first as index 0, is the background past the edges of
+    // frames smaller than the image
+    let mut colors: Vec<Rgb> = frames
+        .iter()
+        .flat_map(|f| f.iter())
+        .map(|(_, c)| c.color)
+        .collect();
+    colors.push(Rgb::default());
+    colors.sort_by_key(|&Rgb(r, g, b)| (r, g, b));
+    colors.dedup();
+
+    if colors.len() <= 256 {
+        let indices = colors
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| (c, i as u8))
+            .collect();
+        return (colors, indices);
+    }
+
+    let reduce = |Rgb(r, g, b): Rgb| (r & 0xe0) | ((g & 0xe0) >> 3) | (b >> 6);
+    let palette = (0..=255u8)
+        .map(|i| Rgb(i & 0xe0, (i << 3) & 0xe0, (i << 6) & 0xc0))
+        .collect();
+    let indices = colors.into_iter().map(|c| (c, reduce(c))).collect();
+    (palette, indices)
+}
+
+/// LZW compression of the colour indices of a GIF image.
+fn lzw(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+
+    let mut output = Vec::new();
+    let (mut buffer, mut bits) = (0u32, 0);
+    let mut emit = |code: u16, size: u8, output: &mut Vec<u8>| {
+        buffer |= (code as u32) << bits;
+        bits += size;
+        while bits >= 8 {
+            output.push(buffer as u8);
+            buffer >>= 8;
+            bits -= 8;
+        }
+    };
+
+    // code of each known sequence followed by an index, 0 when not known yet
+    let mut table = vec![0u16; 4096 << min_code_size];
+    let key = |prefix: u16, index: u8| ((prefix as usize) << min_code_size) | index as usize;
+    let mut code_size = min_code_size + 1;
+    let mut next_code = end + 1;
+    emit(clear, code_size, &mut output);
+
+    let mut current: Option<u16> = None;
+    for &index in indices {
+        let Some(prefix) = current else {
+            current = Some(index as u16);
+            continue;
+        };
+        let code = table[key(prefix, index)];
+        if code != 0 {
+            current = Some(code);
+            continue;
+        }
+
+        emit(prefix, code_size, &mut output);
+        if next_code < 4096 {
+            table[key(prefix, index)] = next_code;
+            next_code += 1;
+            if next_code > 1 << code_size && code_size < 12 {
+                code_size += 1;
+            }
+        } else {
+            emit(clear, code_size, &mut output);
+            table.fill(0);
+            code_size = min_code_size + 1;
+            next_code = end + 1;
+        }
+        current = Some(index as u16);
+    }
+
+    if let Some(prefix) = current {
+        emit(prefix, code_size, &mut output);
+    }
+    emit(end, code_size, &mut output);
+    if bits > 0 {
+        output.push(buffer as u8);
+    }
+    output
+}
+
+fn gif(frames: &[Frame]) -> Vec<u8> {
+    let width = frames.iter().map(|f| size(f).0).max().unwrap_or_default();
+    let height = frames.iter().map(|f| size(f).1).max().unwrap_or_default();
+    let (mut palette, indices) = palette(frames);
+
+    // the palette size is a power of two, of at least 4 colours for the LZW codes
+    let bits = (palette.len().max(4) as u32)
+        .next_power_of_two()
+        .trailing_zeros() as u8;
+    palette.resize(1 << bits, Rgb::default());
+
+    let mut gif = b"GIF89a".to_vec();
+    gif.extend((width as u16).to_le_bytes());
+    gif.extend((height as u16).to_le_bytes());
+    gif.extend([0x80 | 0x70 | (bits - 1), 0, 0]);
+    for Rgb(r, g, b) in palette {
+        gif.extend([r, g, b]);
+    }
+    // loop forever
+    gif.extend(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00");
+
+    for frame in frames {
+        gif.extend([0x21, 0xf9, 0x04, 0x00]);
+        gif.extend(GIF_DELAY.to_le_bytes());
+        gif.extend([0x00, 0x00]);
+
+        gif.push(0x2c);
+        gif.extend([0, 0, 0, 0]);
+        gif.extend((width as u16).to_le_bytes());
+        gif.extend((height as u16).to_le_bytes());
+        gif.push(0);
+
+        let indices = scale(&frame.map(|c| indices[&c.color]), width, height);
+        gif.push(bits);
+        for block in lzw(&indices, bits).chunks(255) {
+            gif.push(block.len() as u8);
+            gif.extend(block);
+        }
+        gif.push(0);
+    }
+
+    gif.push(0x3b);
+    gif
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn frame() -> Frame {
+        let red = Cell::new('#', Rgb(255, 0, 0));
+        let blue = Cell::new('.', Rgb(0, 0, 255));
+        Grid::from_rows(vec![vec![red, blue], vec![blue, blue]]).unwrap()
+    }
+
+    #[test]
+    fn terminal() {
+        colored::control::set_override(false);
+        let mut out = Vec::new();
+        render_terminal(&[frame()], &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "#.\n..\n");
+    }
+
+    #[test]
+    fn images() {
+        let ppm = ppm(&frame());
+        assert!(ppm.starts_with(b"P6\n8 8\n255\n"));
+        assert_eq!(ppm.len(), 11 + 8 * 8 * 3);
+        assert_eq!(ppm[11..14], [255, 0, 0]);
+
+        let png = png(&frame()).unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+        assert!(png.ends_with(b"IEND\xae\x42\x60\x82"));
+
+        let gif = gif(&[frame(), frame()]);
+        assert!(gif.starts_with(b"GIF89a\x08\x00\x08\x00"));
+        assert_eq!(gif.last(), Some(&0x3b));
+    }
+
+    #[test]
+    fn lzw_codes() {
+        // codes 4 (clear), 1, 6, 6 on 3 bits, then 2, 9, 9, 5 (end) on 4 bits
+        let compressed = lzw(&[1, 1, 1, 1, 1, 2, 2, 2, 2, 2], 2);
+        assert_eq!(compressed, [0x8c, 0x2d, 0x99, 0x05]);
+    }
+}
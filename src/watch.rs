@@ -0,0 +1,221 @@
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime};
+use std::{env, fs, thread};
+
+use anyhow::{bail, Result};
+use colored::Colorize;
+
+use crate::args::{RunArgs, WatchArgs};
+use crate::runner::{load_tasks, Status, Statuses, Task};
+use crate::test_case::day_dir;
+
+/// Environment variable used to hand the last statuses over to the rebuilt
+/// binary, so that the first run after a restart can still be compared.
+const STATE_VAR: &str = "AOC_WATCH_STATE";
+
+/// Status of every part that was run, by day, case name and part.
+type State = BTreeMap<(u32, String, u32), Status>;
+
+type Snapshot = Vec<(PathBuf, Option<SystemTime>, u64)>;
+
+fn snapshot(paths: &[PathBuf]) -> Snapshot {
+    fn visit(path: &Path, snapshot: &mut Snapshot) {
+        let Ok(metadata) = fs::metadata(path) else {
+            return;
+        };
+        if metadata.is_dir() {
+            if let Ok(entries) = fs::read_dir(path) {
+                for entry in entries.flatten() {
+                    visit(&entry.path(), snapshot);
+                }
+            }
+        } else {
+            snapshot.push((path.into(), metadata.modified().ok(), metadata.len()));
+        }
+    }
+
+    let mut snapshot = Vec::new();
+    for path in paths {
+        visit(path, &mut snapshot);
+    }
+    snapshot.sort();
+    snapshot
+}
+
+fn input_paths(args: &RunArgs) -> Result<Vec<PathBuf>> {
+    match &args.input {
+        Some(path) if path == Path::new("-") => bail!("cannot watch stdin"),
+        Some(path) => Ok(vec![path.clone()]),
+        None if args.all => Ok(vec![args.input_dir.join(args.year.to_string())]),
+        None => Ok(args
+            .day
+            .map(|day| day_dir(&args.input_dir, args.year, day))
+            .into_iter()
+            .collect()),
+    }
+}
+
+fn collect(tasks: &[Task], statuses: &[Statuses]) -> State {
+    let mut state = State::new();
+    for (task, statuses) in tasks.iter().zip(statuses) {
+        for (part, status) in [1, 2].into_iter().zip(statuses) {
+            if let Some(status) = status {
+                state.insert((task.day, task.case.name(), part), *status);
+            }
+        }
+    }
+    state
+}
+
+fn print_changes(previous: &State, current: &State) {
+    println!("{} status changes", "==>".green().bold());
+
+    let mut changed = false;
+    for (key @ (day, name, part), status) in current {
+        let before = previous.get(key);
+        if before == Some(status) {
+            continue;
+        }
+
+        changed = true;
+        let before = before.map(Status::to_string).unwrap_or("NEW".into());
+        let after = match status {
+            Status::Correct => status.to_string().green(),
+            Status::Unknown => status.to_string().yellow(),
+            _ => status.to_string().red(),
+        };
+        println!(
+            "  {} day {} {} part {}: {} -> {}",
+            "->".blue().bold(),
+            day,
+            name,
+            part,
+            before,
+            after
+        );
+    }
+
+    if !changed {
+        println!("  {} no status changes", "->".blue().bold());
+    }
+}
+
+fn save_state(state: &State, path: &Path) -> io::Result<()> {
+    let content = state
+        .iter()
+        .map(|((day, name, part), status)| format!("{}\t{}\t{}\t{}\n", day, part, status, name))
+        .collect::<String>();
+    fs::write(path, content)
+}
+
+fn load_state() -> Result<State> {
+    let Some(path) = env::var_os(STATE_VAR) else {
+        return Ok(State::new());
+    };
+
+    let content = fs::read_to_string(&path)?;
+    let _ = fs::remove_file(&path);
+
+    let mut state = State::new();
+    for line in content.lines() {
+        let mut fields = line.splitn(4, '\t');
+        let (Some(day), Some(part), Some(status), Some(name)) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            bail!("invalid watch state line: {}", line);
+        };
+        state.insert((day.parse()?, name.into(), part.parse()?), status.parse()?);
+    }
+
+    Ok(state)
+}
+
+fn rebuild() -> Result<bool> {
+    println!("{} rebuilding", "==>".green().bold());
+
+    let mut command = Command::new(env::var("CARGO").unwrap_or("cargo".into()));
+    command.arg("build");
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+
+    Ok(command.status()?.success())
+}
+
+/// Replaces the running process with the freshly built binary at `exe`.
+fn restart(exe: &Path, state: &State) -> Result<()> {
+    let state_path = env::temp_dir().join(format!("aoc-watch-{}.state", std::process::id()));
+    save_state(state, &state_path)?;
+
+    let mut command = Command::new(exe);
+    command.args(env::args_os().skip(1)).env(STATE_VAR, &state_path);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        Err(command.exec().into())
+    }
+
+    #[cfg(not(unix))]
+    {
+        std::process::exit(command.status()?.code().unwrap_or(1));
+    }
+}
+
+/// Runs the selected cases with `run` every time their inputs change, and
+/// with `--rebuild` also rebuilds and restarts every time the sources change.
+pub fn watch<F>(args: &WatchArgs, run: F) -> Result<()>
+where
+    F: Fn(&RunArgs, &[Task]) -> io::Result<Vec<Statuses>>,
+{
+    let inputs = input_paths(&args.run)?;
+    let sources = vec![PathBuf::from("src"), PathBuf::from("Cargo.toml")];
+    let interval = Duration::from_millis(args.interval);
+    // resolved now, since once the binary is rebuilt it points to the deleted file
+    let exe = env::current_exe()?;
+
+    let mut state = load_state()?;
+    let run_once = |state: &mut State| -> Result<()> {
+        let tasks = load_tasks(&args.run)?;
+        let current = collect(&tasks, &run(&args.run, &tasks)?);
+        if !state.is_empty() {
+            print_changes(state, &current);
+        }
+        *state = current;
+        Ok(())
+    };
+
+    let mut sources_snapshot = snapshot(&sources);
+    run_once(&mut state)?;
+    let mut inputs_snapshot = snapshot(&inputs);
+
+    println!("{} watching for changes", "==>".green().bold());
+    loop {
+        thread::sleep(interval);
+
+        if args.rebuild {
+            let current = snapshot(&sources);
+            if current != sources_snapshot {
+                sources_snapshot = current;
+                if rebuild()? {
+                    restart(&exe, &state)?;
+                }
+                println!("{} build failed", "==>".red().bold());
+                continue;
+            }
+        }
+
+        let current = snapshot(&inputs);
+        if current != inputs_snapshot {
+            if let Err(e) = run_once(&mut state) {
+                println!("{} {}", "==>".red().bold(), e);
+            }
+            // taken after the run, so that answers stored by it don't trigger another one
+            inputs_snapshot = snapshot(&inputs);
+            println!("{} watching for changes", "==>".green().bold());
+        }
+    }
+}
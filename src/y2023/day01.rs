@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use crate::problem::AoCProblem;
+use aoc::problem::{Answer, AoCProblem};
 use anyhow::{anyhow, Result};
 
 #[derive(Debug, Default)]
@@ -23,7 +23,7 @@ impl FromStr for AoCDay1 {
 }
 
 impl AoCProblem for AoCDay1 {
-    fn solve_part1(&self) -> Result<String> {
+    fn solve_part1(&self) -> Result<Answer> {
         let mut sum = 0u32;
         for line in &self.lines {
             let numbers: Vec<u32> = line
@@ -38,10 +38,10 @@ impl AoCProblem for AoCDay1 {
             }
         }
 
-        Ok(sum.to_string())
+        Ok(sum.into())
     }
 
-    fn solve_part2(&self) -> Result<String> {
+    fn solve_part2(&self) -> Result<Answer> {
         let mut sum: usize = 0;
         for line in &self.lines {
             let mut digits: Vec<usize> = Vec::new();
@@ -66,6 +66,6 @@ impl AoCProblem for AoCDay1 {
             }
         }
 
-        Ok(sum.to_string())
+        Ok(sum.into())
     }
 }
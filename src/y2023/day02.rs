@@ -1,8 +1,9 @@
 use std::str::FromStr;
 
-use crate::problem::AoCProblem;
+use aoc::problem::{Answer, AoCProblem};
+use aoc::utils::parse_error::ParseError;
 
-use anyhow::{anyhow, bail, Result};
+use anyhow::Result;
 
 #[derive(Debug)]
 struct Color(u32, u32, u32);
@@ -21,21 +22,23 @@ impl FromStr for AoCDay2 {
 
     fn from_str(s: &str) -> Result<Self> {
         let mut result = AoCDay2::default();
-        for line in s.lines() {
+        for (line_number, line) in s.lines().enumerate() {
             let mut games: Vec<Color> = Vec::new();
-            for game in line
-                .split(':')
-                .nth(1)
-                .ok_or(anyhow!("invalid input"))?
-                .split(';')
-            {
+            let (_, rounds) = line
+                .split_once(':')
+                .ok_or_else(|| ParseError::expected(s, line_number, line.chars().count(), "':'"))?;
+            for game in rounds.split(';') {
                 let mut r: u32 = 0;
                 let mut g: u32 = 0;
                 let mut b: u32 = 0;
                 for color_line in game.split(',') {
-                    let parts = color_line.trim().split(' ').collect::<Vec<&str>>();
-                    let number = parts[0].parse::<u32>()?;
-                    let color = parts[1];
+                    let color_line = color_line.trim();
+                    let (number, color) = color_line.split_once(' ').ok_or_else(|| {
+                        ParseError::at_token(s, color_line, "expected number and color")
+                    })?;
+                    let number = number
+                        .parse::<u32>()
+                        .map_err(|_| ParseError::at_token(s, number, "expected number"))?;
 
                     match color {
                         "red" => {
@@ -48,7 +51,8 @@ impl FromStr for AoCDay2 {
                             b = number;
                         }
                         _ => {
-                            bail!("invalid input");
+                            let error = ParseError::at_token(s, color, "expected red, green or blue");
+                            return Err(error.into());
                         }
                     }
                 }
@@ -63,7 +67,7 @@ impl FromStr for AoCDay2 {
 }
 
 impl AoCProblem for AoCDay2 {
-    fn solve_part1(&self) -> Result<String> {
+    fn solve_part1(&self) -> Result<Answer> {
         let mut result: usize = 0;
         for (id, rounds) in self.games.iter().enumerate() {
             if rounds.iter().all(|&Color(r, g, b)| r <= R && g <= G && b <= B) {
@@ -71,11 +75,11 @@ impl AoCProblem for AoCDay2 {
             }
         }
 
-        Ok(result.to_string())
+        Ok(result.into())
     }
 
-    fn solve_part2(&self) -> Result<String> {
-        let mut result: u32 = 0;
+    fn solve_part2(&self) -> Result<Answer> {
+        let mut result: u64 = 0;
         for game in &self.games {
             let (mut max_r, mut max_g, mut max_b) = (0u32, 0u32, 0u32);
             for &Color(r, g, b) in game {
@@ -83,9 +87,24 @@ impl AoCProblem for AoCDay2 {
                 max_b = max_b.max(b);
                 max_g = max_g.max(g);
             }
-            result += max_r * max_b * max_g;
+            result += max_r as u64 * max_b as u64 * max_g as u64;
         }
 
-        Ok(result.to_string())
+        Ok(result.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn error_columns() {
+        let error = |input: &str| {
+            let error = input.parse::<AoCDay2>().unwrap_err();
+            error.downcast::<ParseError>().unwrap().column
+        };
+        assert_eq!(error("Game 1: 3 blue\nGame 2 3 red"), 12);
+        assert_eq!(error("Gamé 1 3 red"), 12);
     }
 }
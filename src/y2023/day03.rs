@@ -0,0 +1,127 @@
+use std::{collections::HashSet, str::FromStr};
+
+use aoc::problem::{Answer, AoCProblem};
+use aoc::utils::grid::{Grid, Position};
+use aoc::utils::parse_error::ParseError;
+use anyhow::Result;
+
+#[derive(Debug, Copy, Clone)]
+enum GridCell {
+    Empty,
+    Symbol(char),
+    Number(u32, u32),
+}
+
+#[derive(Debug, Default)]
+pub struct AoCDay3 {
+    grid: Grid<GridCell>,
+}
+
+impl AoCDay3 {
+    fn near_cells(&self, position: Position) -> impl Iterator<Item = GridCell> + '_ {
+        self.grid
+            .neighbours8(position)
+            .map(|position| self.grid[position])
+    }
+}
+
+impl FromStr for AoCDay3 {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut id = 0;
+        let mut grid = Vec::new();
+        for (y, line) in s.lines().enumerate() {
+            let mut row: Vec<GridCell> = Vec::new();
+            let mut number: u32 = 0;
+            let mut i: u32 = 0;
+
+            for c in line.chars() {
+                if c.is_ascii_digit() {
+                    number = number * 10 + c.to_digit(10).unwrap();
+                    i += 1;
+                } else {
+                    for _ in 0..i {
+                        row.push(GridCell::Number(id, number));
+                    }
+                    id += 1;
+                    number = 0;
+                    i = 0;
+
+                    if c == '.' {
+                        row.push(GridCell::Empty);
+                    } else {
+                        row.push(GridCell::Symbol(c));
+                    }
+                }
+            }
+
+            for _ in 0..i {
+                row.push(GridCell::Number(id, number));
+            }
+
+            let width = grid.first().map_or(row.len(), Vec::len);
+            if row.len() != width {
+                let message = format!("expected {} cells, found {}", width, row.len());
+                return Err(ParseError::new(s, y, row.len().min(width), message).into());
+            }
+            grid.push(row);
+        }
+
+        let grid = Grid::from_rows(grid).expect("rows of the same length");
+        Ok(Self { grid })
+    }
+}
+
+impl AoCProblem for AoCDay3 {
+    fn solve_part1(&self) -> Result<Answer> {
+        let mut result: u32 = 0;
+        let mut already_sum = HashSet::new();
+        for (position, cell) in self.grid.iter() {
+            if let GridCell::Symbol(_) = cell {
+                for c in self.near_cells(position) {
+                    if let GridCell::Number(id, n) = c {
+                        if !already_sum.contains(&id) {
+                            already_sum.insert(id);
+                            result += n;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(result.into())
+    }
+
+    fn solve_part2(&self) -> Result<Answer> {
+        let mut result: u32 = 0;
+        for (position, cell) in self.grid.iter() {
+            if let GridCell::Symbol('*') = cell {
+                let mut nears: HashSet<(u32, u32)> = HashSet::new();
+                for c in self.near_cells(position) {
+                    if let GridCell::Number(id, n) = c {
+                        nears.insert((id, n));
+                    }
+                }
+                if nears.len() == 2 {
+                    result += nears.iter().map(|&(_, x)| x).product::<u32>();
+                }
+            }
+        }
+
+        Ok(result.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn different_lengths() {
+        let error = "467..\n..*.\n.35..".parse::<AoCDay3>().unwrap_err();
+        let error = error.downcast::<ParseError>().unwrap();
+        assert_eq!((error.line, error.column), (1, 4));
+        assert_eq!(error.message, "expected 5 cells, found 4");
+    }
+}
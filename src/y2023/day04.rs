@@ -1,8 +1,8 @@
 use std::str::FromStr;
 
-use crate::utils::tokenizer::StringTokenizer;
-use crate::{problem::AoCProblem, utils::tokenizer::Tokenizer};
-use anyhow::Result;
+use aoc::problem::{Answer, AoCProblem};
+use aoc::utils::tokenizer::{StringTokenizer, Tokenizer};
+use anyhow::{anyhow, Result};
 
 #[derive(Debug)]
 struct Game {
@@ -20,20 +20,28 @@ impl FromStr for AoCDay4 {
 
     fn from_str(s: &str) -> Result<Self> {
         let mut games = Vec::new();
-        for line in s.lines() {
-            let mut tokenizer = StringTokenizer::from(line);
-            tokenizer.read_until(':');
+        let mut tokenizer = StringTokenizer::from(s);
+        tokenizer.skip_whitespace();
+        while !tokenizer.is_at_end() {
+            tokenizer.expect("Card")?;
+            tokenizer.next_int::<u32>()?;
+            tokenizer.expect(":")?;
 
             let mut own_numbers: Vec<u32> = Vec::new();
+            tokenizer.skip_spaces();
             while tokenizer.peek_char() != Some('|') {
-                own_numbers.push(tokenizer.next_digit().parse()?);
-                tokenizer.skip_whitespace();
+                own_numbers.push(tokenizer.next_int()?);
+                tokenizer.skip_spaces();
             }
+            tokenizer.expect("|")?;
 
             let mut winning_numbers: Vec<u32> = Vec::new();
-            while tokenizer.peek_char().is_some() {
-                winning_numbers.push(tokenizer.next_digit().parse()?);
+            tokenizer.skip_spaces();
+            while !matches!(tokenizer.peek_char(), None | Some('\n' | '\r')) {
+                winning_numbers.push(tokenizer.next_int()?);
+                tokenizer.skip_spaces();
             }
+            tokenizer.skip_whitespace();
 
             games.push(Game {
                 winning_numbers,
@@ -46,15 +54,15 @@ impl FromStr for AoCDay4 {
 }
 
 impl AoCProblem for AoCDay4 {
-    fn solve_part1(&self) -> Result<String> {
-        let mut points: u32 = 0;
+    fn solve_part1(&self) -> Result<Answer> {
+        let mut points: u64 = 0;
 
         for game in &self.games {
             let winning_games: u32 = game
                 .own_numbers
                 .iter()
                 .map(|&n| {
-                    if game.winning_numbers.iter().any(|&w| w == n) {
+                    if game.winning_numbers.contains(&n) {
                         1
                     } else {
                         0
@@ -62,21 +70,23 @@ impl AoCProblem for AoCDay4 {
                 })
                 .sum();
             if winning_games > 0 {
-                points += 2_u32.pow(winning_games - 1);
+                points += 1u64
+                    .checked_shl(winning_games - 1)
+                    .ok_or(anyhow!("too many winning numbers"))?;
             }
         }
 
-        Ok(points.to_string())
+        Ok(points.into())
     }
 
-    fn solve_part2(&self) -> Result<String> {
+    fn solve_part2(&self) -> Result<Answer> {
         let mut multiplier = vec![1usize; self.games.len()];
         for (i, game) in self.games.iter().enumerate() {
             let winning_games: usize = game
                 .own_numbers
                 .iter()
                 .map(|&n| {
-                    if game.winning_numbers.iter().any(|&w| w == n) {
+                    if game.winning_numbers.contains(&n) {
                         1
                     } else {
                         0
@@ -88,6 +98,6 @@ impl AoCProblem for AoCDay4 {
             }
         }
 
-        Ok(multiplier.iter().sum::<usize>().to_string())
+        Ok(multiplier.iter().sum::<usize>().into())
     }
 }
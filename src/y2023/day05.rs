@@ -0,0 +1,214 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    str::FromStr,
+};
+
+use aoc::problem::{Answer, AoCProblem};
+use aoc::trace::Level;
+use aoc::{debug, trace};
+use aoc::utils::grammar::{error, FromPair};
+use aoc::utils::interval::{Interval, IntervalSet, PiecewiseMap};
+use aoc::utils::parse_error::ParseError;
+use anyhow::{anyhow, bail, Result};
+
+use pest::{iterators::Pair, Parser};
+use pest_derive::Parser;
+
+#[derive(Parser)]
+#[grammar = "src/utils/grammar.pest"]
+#[grammar = "src/y2023/day05.pest"]
+struct Day05Parser;
+
+#[derive(Debug)]
+struct Conversion {
+    source_start: u64,
+    destination_start: u64,
+    length: u64,
+}
+
+impl FromPair<Rule> for Conversion {
+    fn from_pair(node: Pair<'_, Rule>) -> Result<Self, ParseError> {
+        let numbers: Vec<u64> = Vec::from_pair(node.clone())?;
+        let [destination_start, source_start, length] = <[u64; 3]>::try_from(numbers)
+            .map_err(|_| error(&node, "expected 3 numbers"))?;
+
+        Ok(Conversion {
+            destination_start,
+            source_start,
+            length,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct Mapping {
+    from: String,
+    to: String,
+    map: PiecewiseMap<u64>,
+}
+
+impl FromPair<Rule> for Mapping {
+    fn from_pair(node: Pair<'_, Rule>) -> Result<Self, ParseError> {
+        let mut inner_nodes: VecDeque<Pair<'_, Rule>> = node.clone().into_inner().collect();
+        let mut category = || {
+            inner_nodes
+                .pop_front()
+                .ok_or_else(|| error(&node, "expected category"))
+                .and_then(String::from_pair)
+        };
+
+        let mut mapping = Mapping {
+            from: category()?,
+            to: category()?,
+            map: PiecewiseMap::new(),
+        };
+
+        for inner_node in inner_nodes {
+            mapping.add(&Conversion::from_pair(inner_node)?);
+        }
+
+        Ok(mapping)
+    }
+}
+
+impl Mapping {
+    fn add(&mut self, conversion: &Conversion) {
+        let source = Interval::new(
+            conversion.source_start,
+            conversion.source_start + conversion.length,
+        );
+        self.map.add(source, conversion.destination_start);
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct AoCDay5 {
+    seeds: Vec<u64>,
+    mapping: HashMap<String, Mapping>,
+}
+
+impl AoCDay5 {
+    /// Locations of the seeds in `seeds`, following the mappings from "seed".
+    fn locations(&self, seeds: IntervalSet<u64>) -> IntervalSet<u64> {
+        let mut category = "seed";
+        let mut values = seeds;
+        while let Some(mapping) = self.mapping.get(category) {
+            values = mapping.map.map_set(&values);
+            if trace::enabled(Level::Debug) {
+                trace::count("intervals mapped", values.iter().count() as u64);
+            }
+            debug!("{} -> {}: {} intervals", category, mapping.to, values.iter().count());
+            trace!("{:?}", values.iter().collect::<Vec<_>>());
+            category = &mapping.to;
+        }
+        values
+    }
+
+    fn lowest_location(&self, seeds: IntervalSet<u64>) -> Result<Answer> {
+        let location = self.locations(seeds).min().ok_or(anyhow!("no seeds"))?;
+        Ok(location.into())
+    }
+}
+
+impl FromStr for AoCDay5 {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut result = AoCDay5::default();
+        let parsed = Day05Parser::parse(Rule::input, s).map_err(ParseError::from)?;
+        for pair in parsed {
+            match pair.as_rule() {
+                Rule::seeds => {
+                    result.seeds = Vec::from_pair(pair)?;
+                }
+                Rule::map => {
+                    let mapping = Mapping::from_pair(pair)?;
+                    result.mapping.insert(mapping.from.clone(), mapping);
+                }
+                _ => {
+                    bail!("parsing error");
+                }
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+impl AoCProblem for AoCDay5 {
+    fn solve_part1(&self) -> Result<Answer> {
+        let seeds = self
+            .seeds
+            .iter()
+            .map(|&seed| Interval::new(seed, seed + 1))
+            .collect();
+        self.lowest_location(seeds)
+    }
+
+    fn solve_part2(&self) -> Result<Answer> {
+        let seeds = self
+            .seeds
+            .chunks_exact(2)
+            .map(|range| Interval::new(range[0], range[0] + range[1]))
+            .collect();
+        self.lowest_location(seeds)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn mapping(conversions: &[(u64, u64, u64)]) -> Mapping {
+        let mut mapping = Mapping {
+            from: "a".into(),
+            to: "b".into(),
+            map: PiecewiseMap::new(),
+        };
+        for &(destination_start, source_start, length) in conversions {
+            mapping.add(&Conversion {
+                destination_start,
+                source_start,
+                length,
+            });
+        }
+        mapping
+    }
+
+    #[test]
+    fn parse_input() {
+        let sample_input = include_str!("../../input/2023/05/example.txt");
+        let result = Day05Parser::parse(super::Rule::input, sample_input);
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_mapping() {
+        let mapping = mapping(&[(50, 98, 2)]);
+
+        assert_eq!(mapping.map.map(98), 50);
+        assert_eq!(mapping.map.map(99), 51);
+        assert_eq!(mapping.map.map(100), 100);
+    }
+
+    #[test]
+    fn fills_void() {
+        let mapping = mapping(&[(50, 1, 2), (60, 5, 1), (70, 6, 2)]);
+
+        let seeds = IntervalSet::from(Interval::new(0, 10));
+        let expected = [(0, 1), (3, 5), (8, 10), (50, 52), (60, 61), (70, 72)]
+            .into_iter()
+            .map(|(a, b)| Interval::new(a, b))
+            .collect();
+        assert_eq!(mapping.map.map_set(&seeds), expected);
+    }
+
+    #[test]
+    fn test_ranges_in() {
+        let mapping = mapping(&[(50, 1, 2), (60, 5, 1), (70, 6, 2)]);
+
+        let seeds = IntervalSet::from(Interval::new(3, 5));
+        assert_eq!(mapping.map.map_set(&seeds), seeds);
+    }
+}
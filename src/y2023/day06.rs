@@ -1,32 +1,18 @@
 use std::str::FromStr;
 
-use crate::problem::AoCProblem;
+use aoc::problem::{Answer, AoCProblem};
+use aoc::utils::grammar::FromPair;
+use aoc::utils::parse_error::ParseError;
 use anyhow::{bail, Result};
 
-use pest::{iterators::Pair, Parser};
+use pest::Parser;
 use pest_derive::Parser;
 
 #[derive(Parser)]
-#[grammar = "src/days/day06.pest"]
+#[grammar = "src/utils/grammar.pest"]
+#[grammar = "src/y2023/day06.pest"]
 struct Day06Parser;
 
-trait Parse {
-    fn parse(node: Pair<'_, Rule>) -> Result<Self>
-    where
-        Self: Sized;
-}
-
-impl Parse for Vec<u32> {
-    fn parse(node: Pair<'_, Rule>) -> Result<Self>
-    where
-        Self: Sized,
-    {
-        node.into_inner()
-            .map(|n| n.as_str().parse::<u32>().map_err(anyhow::Error::from))
-            .collect::<Result<Vec<u32>>>()
-    }
-}
-
 #[derive(Debug, Default)]
 pub struct AoCDay6 {
     time: Vec<u32>,
@@ -59,14 +45,14 @@ impl FromStr for AoCDay6 {
 
     fn from_str(s: &str) -> Result<Self> {
         let mut result = AoCDay6::default();
-        let parsed = Day06Parser::parse(Rule::input, s)?;
+        let parsed = Day06Parser::parse(Rule::input, s).map_err(ParseError::from)?;
         for pair in parsed {
             match pair.as_rule() {
                 Rule::time => {
-                    result.time = Vec::<u32>::parse(pair)?;
+                    result.time = Vec::from_pair(pair)?;
                 }
                 Rule::distance => {
-                    result.distance = Vec::<u32>::parse(pair)?;
+                    result.distance = Vec::from_pair(pair)?;
                 }
                 _ => {
                     bail!("parsing error");
@@ -78,17 +64,17 @@ impl FromStr for AoCDay6 {
 }
 
 impl AoCProblem for AoCDay6 {
-    fn solve_part1(&self) -> Result<String> {
+    fn solve_part1(&self) -> Result<Answer> {
         Ok(self
             .time
             .iter()
             .zip(self.distance.iter())
             .map(|(&time, &record_distance)| solve(time as u64, record_distance as u64))
             .product::<u64>()
-            .to_string())
+            .into())
     }
 
-    fn solve_part2(&self) -> Result<String> {
+    fn solve_part2(&self) -> Result<Answer> {
         let real_time: u64 = self
             .time
             .iter()
@@ -102,7 +88,7 @@ impl AoCProblem for AoCDay6 {
             .fold(String::new(), |acc, f| acc + &f)
             .parse::<u64>()?;
 
-        Ok(solve(real_time, real_distance).to_string())
+        Ok(solve(real_time, real_distance).into())
     }
 }
 
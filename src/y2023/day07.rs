@@ -1,8 +1,12 @@
 use std::{cmp::Ordering, collections::HashMap, str::FromStr};
 
-use crate::problem::AoCProblem;
+use aoc::generate::Rng;
+use aoc::problem::{Answer, AoCProblem};
+use aoc::utils::parse_error::ParseError;
 use anyhow::Result;
 
+const CARDS: &str = "AKQJT98765432";
+
 #[derive(Debug, Clone)]
 struct Hand(Vec<char>);
 
@@ -171,38 +175,62 @@ impl FromStr for AoCDay7 {
 
     fn from_str(s: &str) -> Result<Self> {
         let mut games = Vec::new();
-        for line in s.lines() {
-            if let Some((cards, score)) = line.split_once(' ') {
-                games.push(Game(Hand(cards.chars().collect()), score.parse()?));
+        for (line_number, line) in s.lines().enumerate() {
+            let (cards, bet) = line
+                .split_once(' ')
+                .ok_or_else(|| ParseError::expected(s, line_number, line.chars().count(), "bet"))?;
+
+            let invalid = cards.chars().enumerate().find(|&(_, c)| !CARDS.contains(c));
+            if let Some((column, _)) = invalid {
+                return Err(ParseError::expected(s, line_number, column, "card").into());
             }
+            let bet = bet
+                .parse()
+                .map_err(|_| ParseError::at_token(s, bet, "invalid bet"))?;
+
+            games.push(Game(Hand(cards.chars().collect()), bet));
         }
         Ok(Self { games })
     }
 }
 
 impl AoCProblem for AoCDay7 {
-    fn solve_part1(&self) -> Result<String> {
+    const GENERATES: bool = true;
+
+    fn solve_part1(&self) -> Result<Answer> {
         let mut game_with_rank = self.games.clone();
         game_with_rank.sort();
 
         Ok(game_with_rank
             .iter()
             .enumerate()
-            .map(|(rank, Game(_, bet))| bet * (rank as u32 + 1))
-            .sum::<u32>()
-            .to_string())
+            .map(|(rank, Game(_, bet))| *bet as u64 * (rank as u64 + 1))
+            .sum::<u64>()
+            .into())
     }
 
-    fn solve_part2(&self) -> Result<String> {
+    fn solve_part2(&self) -> Result<Answer> {
         let mut game_with_rank = self.games.clone();
         game_with_rank.sort_by(|a, b| cmp_j(&a.0, &b.0));
 
         Ok(game_with_rank
             .iter()
             .enumerate()
-            .map(|(rank, Game(_, bet))| bet * (rank as u32 + 1))
-            .sum::<u32>()
-            .to_string())
+            .map(|(rank, Game(_, bet))| *bet as u64 * (rank as u64 + 1))
+            .sum::<u64>()
+            .into())
+    }
+
+    fn generate_input(size: usize, rng: &mut Rng) -> String {
+        let cards: Vec<char> = CARDS.chars().collect();
+        let mut input = String::new();
+        for _ in 0..size {
+            // few distinct cards per hand, so that every kind of hand shows up
+            let pool: Vec<char> = (0..rng.range(1..=5)).map(|_| *rng.choose(&cards)).collect();
+            input.extend((0..5).map(|_| *rng.choose(&pool)));
+            input.push_str(&format!(" {}\n", rng.range(1..=1000)));
+        }
+        input
     }
 }
 
@@ -254,4 +282,14 @@ mod test {
         assert!(Hand(vec!['A', 'A', 'A', 'A', 'A']) == Hand(vec!['A', 'A', 'A', 'A', 'A']));
         assert!(Hand(vec!['A', 'A', 'A', 'A', 'K']) < Hand(vec!['A', 'A', 'A', 'A', 'A']));
     }
+
+    #[test]
+    fn error_columns() {
+        let error = |input: &str| {
+            let error = input.parse::<AoCDay7>().unwrap_err();
+            error.downcast::<ParseError>().unwrap().column
+        };
+        assert_eq!(error("AKQJT 1\nAK1QT 2"), 2);
+        assert_eq!(error("AKQéT"), 5);
+    }
 }
@@ -0,0 +1,180 @@
+use std::{collections::HashMap, str::FromStr};
+
+use aoc::problem::{Answer, AoCProblem};
+use aoc::utils::math::crt;
+use aoc::utils::parse_error::ParseError;
+use anyhow::{anyhow, Result};
+use sscanf::sscanf;
+
+const START: &str = "AAA";
+const DESTINATION: &str = "ZZZ";
+
+#[derive(Debug, Clone, Copy)]
+enum Turn {
+    Left,
+    Right,
+}
+
+#[derive(Debug)]
+struct Node(String, String);
+
+impl Node {
+    fn move_to(&self, turn: Turn) -> &str {
+        match turn {
+            Turn::Left => &self.0,
+            Turn::Right => &self.1,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct AoCDay8 {
+    directions: Vec<Turn>,
+    nodes: HashMap<String, Node>,
+}
+
+/// Steps at which a ghost is on a node ending with `Z`: once before its path
+/// loops, then periodically.
+#[derive(Debug)]
+struct Ghost {
+    hits_before_cycle: Vec<usize>,
+    cycle_start: usize,
+    cycle_length: usize,
+    cycle_hits: Vec<usize>,
+}
+
+impl Ghost {
+    fn at_destination(&self, step: usize) -> bool {
+        if step < self.cycle_start {
+            self.hits_before_cycle.contains(&step)
+        } else {
+            let step = self.cycle_start + (step - self.cycle_start) % self.cycle_length;
+            self.cycle_hits.contains(&step)
+        }
+    }
+}
+
+impl AoCDay8 {
+    fn ghost(&self, start: &str) -> Result<Ghost> {
+        let mut seen: HashMap<(&str, usize), usize> = HashMap::new();
+        let mut hits = Vec::new();
+        let mut node = start;
+        let mut step = 0;
+        loop {
+            let index = step % self.directions.len();
+            if let Some(&cycle_start) = seen.get(&(node, index)) {
+                let (hits_before_cycle, cycle_hits) = hits.iter().partition(|&&h| h < cycle_start);
+                return Ok(Ghost {
+                    hits_before_cycle,
+                    cycle_start,
+                    cycle_length: step - cycle_start,
+                    cycle_hits,
+                });
+            }
+            seen.insert((node, index), step);
+
+            if node.ends_with('Z') {
+                hits.push(step);
+            }
+            node = self
+                .nodes
+                .get(node)
+                .ok_or(anyhow!("node not found"))?
+                .move_to(self.directions[index]);
+            step += 1;
+        }
+    }
+}
+
+impl FromStr for AoCDay8 {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let lines = s.lines().collect::<Vec<&str>>();
+        if lines.first().is_none_or(|line| line.is_empty()) {
+            return Err(ParseError::expected(s, 0, 0, "directions").into());
+        }
+
+        let mut result = AoCDay8::default();
+        for (column, c) in lines[0].chars().enumerate() {
+            let direction = match c {
+                'L' => Turn::Left,
+                'R' => Turn::Right,
+                _ => return Err(ParseError::expected(s, 0, column, "direction L or R").into()),
+            };
+            result.directions.push(direction);
+        }
+
+        for (line_number, line) in lines.iter().enumerate().skip(2) {
+            let (from, left, right) = sscanf!(line, "{str} = ({str}, {str})").map_err(|_| {
+                ParseError::expected(s, line_number, 0, "node as NODE = (LEFT, RIGHT)")
+            })?;
+            result
+                .nodes
+                .insert(from.into(), Node(left.into(), right.into()));
+        }
+
+        Ok(result)
+    }
+}
+
+impl AoCProblem for AoCDay8 {
+    fn solve_part1(&self) -> Result<Answer> {
+        let mut current_node = START;
+        let mut step: usize = 0;
+        while current_node != DESTINATION {
+            let direction = self.directions[step % self.directions.len()];
+            let node = self
+                .nodes
+                .get(current_node)
+                .ok_or(anyhow!("node not found"))?;
+            current_node = node.move_to(direction);
+            step += 1;
+        }
+
+        Ok(step.into())
+    }
+
+    fn solve_part2(&self) -> Result<Answer> {
+        let ghosts = self
+            .nodes
+            .keys()
+            .filter(|k| k.ends_with('A'))
+            .map(|k| self.ghost(k))
+            .collect::<Result<Vec<Ghost>>>()?;
+
+        // before every ghost loops, the ghosts can only meet on a known hit
+        let early = ghosts
+            .iter()
+            .flat_map(|g| &g.hits_before_cycle)
+            .filter(|&&step| ghosts.iter().all(|g| g.at_destination(step)))
+            .min();
+        if let Some(&step) = early {
+            return Ok(step.into());
+        }
+
+        // afterwards, every combination of hits in the cycles is a congruence system
+        let mut systems: Vec<Vec<(i64, i64)>> = vec![Vec::new()];
+        for ghost in &ghosts {
+            systems = systems
+                .into_iter()
+                .flat_map(|system| {
+                    ghost.cycle_hits.iter().map(move |&hit| {
+                        let mut system = system.clone();
+                        system.push((hit as i64, ghost.cycle_length as i64));
+                        system
+                    })
+                })
+                .collect();
+        }
+
+        let lower_bound = ghosts.iter().map(|g| g.cycle_start).max().unwrap_or(0) as i64;
+        systems
+            .into_iter()
+            .filter_map(crt)
+            .map(|(x, m)| x + ((lower_bound - x).max(0) + m - 1) / m * m)
+            .min()
+            .map(Answer::from)
+            .ok_or(anyhow!("no solution"))
+    }
+}
@@ -1,6 +1,8 @@
-use std::{num::ParseIntError, str::FromStr};
+use std::str::FromStr;
 
-use crate::problem::AoCProblem;
+use aoc::generate::Rng;
+use aoc::problem::{Answer, AoCProblem};
+use aoc::utils::parse_error::ParseError;
 use anyhow::Result;
 
 #[derive(Debug, Default)]
@@ -55,30 +57,50 @@ impl FromStr for AoCDay9 {
                 .lines()
                 .map(|line| {
                     line.split_ascii_whitespace()
-                        .map(|v| v.parse::<i64>())
-                        .collect::<Result<Vec<i64>, ParseIntError>>()
+                        .map(|v| {
+                            v.parse::<i64>()
+                                .map_err(|_| ParseError::at_token(s, v, "expected number"))
+                        })
+                        .collect::<Result<Vec<i64>, ParseError>>()
                 })
-                .collect::<Result<Vec<Vec<i64>>, ParseIntError>>()?,
+                .collect::<Result<Vec<Vec<i64>>, ParseError>>()?,
         })
     }
 }
 
 impl AoCProblem for AoCDay9 {
-    fn solve_part1(&self) -> Result<String> {
+    const GENERATES: bool = true;
+
+    fn solve_part1(&self) -> Result<Answer> {
         Ok(self
             .values
             .iter()
             .map(|v| get_next_value(v))
             .sum::<i64>()
-            .to_string())
+            .into())
     }
 
-    fn solve_part2(&self) -> Result<String> {
+    fn solve_part2(&self) -> Result<Answer> {
         Ok(self
             .values
             .iter()
             .map(|v| get_prev_value(v))
             .sum::<i64>()
-            .to_string())
+            .into())
+    }
+
+    fn generate_input(size: usize, rng: &mut Rng) -> String {
+        let mut input = String::new();
+        for _ in 0..size {
+            // values of a random polynomial of degree at most 5
+            let coefficients: Vec<i64> = (0..rng.range(1..=6)).map(|_| rng.range(-9..=9)).collect();
+            let values: Vec<String> = (0..21)
+                .map(|x| coefficients.iter().rev().fold(0, |acc, &c| acc * x + c))
+                .map(|v| v.to_string())
+                .collect();
+            input.push_str(&values.join(" "));
+            input.push('\n');
+        }
+        input
     }
 }
@@ -0,0 +1,219 @@
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
+use aoc::problem::{Answer, AoCProblem};
+use aoc::utils::geom::{Direction, Point2};
+use aoc::utils::graph::{bfs, Graph};
+use aoc::utils::grid::Grid;
+use aoc::visualize::{self, Frame, Rgb, Visualize};
+use anyhow::{bail, Error, Result};
+
+type Point = Point2<i32>;
+
+/// Box drawing character of a pipe.
+fn glyph(c: char) -> char {
+    match c {
+        '-' => '─',
+        '|' => '│',
+        'F' => '┌',
+        '7' => '┐',
+        'L' => '└',
+        'J' => '┘',
+        c => c,
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Cell {
+    Empty,
+    Pipe,
+    Loop,
+}
+
+#[derive(Debug, Default)]
+pub struct AoCDay10 {
+    map: Grid<Cell>,
+    cycle_length: u32,
+    input: Grid<char>,
+    /// distance of every cell of the loop from the start
+    distances: HashMap<Point, usize>,
+}
+
+impl FromStr for AoCDay10 {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let mut start = Point::default();
+        let mut edges: HashSet<(Point, Point)> = HashSet::new();
+        let lines = Grid::parse(input, |c| "|-LJ7F.S".contains(c).then_some(c))?;
+
+        for ((y, x), &c) in lines.iter() {
+            let p = Point::new(x as i32, y as i32);
+            let directions: &[Direction] = match c {
+                'S' => &Direction::ALL,
+                '-' => &[Direction::Left, Direction::Right],
+                '|' => &[Direction::Up, Direction::Down],
+                'F' => &[Direction::Down, Direction::Right],
+                'L' => &[Direction::Up, Direction::Right],
+                'J' => &[Direction::Up, Direction::Left],
+                '7' => &[Direction::Left, Direction::Down],
+                _ => &[],
+            };
+
+            if c == 'S' {
+                start = p;
+            }
+            for &direction in directions {
+                edges.insert((p, p + direction));
+            }
+        }
+        let map = lines.map(|&c| if c == '.' { Cell::Empty } else { Cell::Pipe });
+
+        // pipes are only connected when both ends point to each other
+        let mut graph = Graph::new();
+        for &(a, b) in edges.iter() {
+            if edges.contains(&(b, a)) {
+                graph.add_edge(a, b, 1);
+            }
+        }
+
+        let mut problem = Self {
+            map,
+            cycle_length: 0,
+            input: lines,
+            distances: HashMap::new(),
+        };
+
+        // the farthest point of the loop is halfway around it
+        let distances = bfs(start, |p| graph.neighbours(p).map(|(&n, _)| n).collect::<Vec<_>>());
+        for p in distances.keys() {
+            problem.map[(p.y as usize, p.x as usize)] = Cell::Loop;
+        }
+        problem.cycle_length = distances.values().copied().max().unwrap_or_default() as u32;
+        problem.distances = distances;
+
+        Ok(problem)
+    }
+}
+
+impl AoCProblem for AoCDay10 {
+    fn solve_part1(&self) -> Result<Answer> {
+        Ok(self.cycle_length.into())
+    }
+
+    fn solve_part2(&self) -> Result<Answer> {
+        let count = self.enclosed()?.iter().filter(|(_, &inside)| inside).count();
+        Ok(count.into())
+    }
+
+    fn summary(&self) -> String {
+        let start = self.distances.iter().find(|(_, &d)| d == 0).map(|(p, _)| *p);
+        let start = start.unwrap_or_default();
+        let mut summary = format!(
+            "{}x{} grid, start at ({}, {}), loop of {} pipes\n",
+            self.input.width(),
+            self.input.height(),
+            start.x,
+            start.y,
+            self.distances.len()
+        );
+        // the loop drawn with box characters, the other pipes as +
+        for (row, chars) in self.map.rows().zip(self.input.rows()) {
+            summary.extend(row.iter().zip(chars).map(|(&cell, &c)| match cell {
+                Cell::Loop => glyph(c),
+                Cell::Pipe => '+',
+                Cell::Empty => '.',
+            }));
+            summary.push('\n');
+        }
+        summary
+    }
+
+    fn visualization(&self) -> Option<&dyn Visualize> {
+        Some(self)
+    }
+}
+
+impl Visualize for AoCDay10 {
+    fn frames(&self, part: u32) -> Vec<Frame> {
+        const LOOP: Rgb = Rgb(255, 200, 0);
+        const INSIDE: Rgb = Rgb(0, 200, 80);
+        const PIPE: Rgb = Rgb(90, 90, 90);
+        const EMPTY: Rgb = Rgb(30, 30, 30);
+
+        let frame = |visible: &dyn Fn(Point) -> bool, enclosed: Option<&Grid<bool>>| {
+            let color = |y: usize, x: usize, c: char| {
+                if visible(Point::new(x as i32, y as i32)) {
+                    LOOP
+                } else if enclosed.is_some_and(|e| e[(y, x)]) {
+                    INSIDE
+                } else if c == '.' {
+                    EMPTY
+                } else {
+                    PIPE
+                }
+            };
+            let rows = self.input.rows().enumerate().map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(x, &c)| visualize::Cell::new(glyph(c), color(y, x, c)))
+                    .collect()
+            });
+            Grid::from_rows(rows.collect()).unwrap_or_default()
+        };
+
+        if part == 2 {
+            // without the inside when the loop is malformed, part 2 reports why
+            let enclosed = self.enclosed().ok();
+            return vec![frame(&|p| self.distances.contains_key(&p), enclosed.as_ref())];
+        }
+
+        // the loop grows from the start in both directions, in about 20 frames
+        let steps = self.cycle_length as usize;
+        let step = steps.div_ceil(20).max(1);
+        (0..=steps.div_ceil(step))
+            .map(|i| {
+                let reached = |p| self.distances.get(&p).is_some_and(|&d| d <= i * step);
+                frame(&reached, None)
+            })
+            .collect()
+    }
+}
+
+impl AoCDay10 {
+    /// Cells enclosed by the loop: a scan of each row is inside after
+    /// crossing the loop an odd number of times.
+    fn enclosed(&self) -> Result<Grid<bool>> {
+        let mut enclosed = Grid::new(self.map.width(), self.map.height(), false);
+        for (y, (row, chars)) in self.map.rows().zip(self.input.rows()).enumerate() {
+            let mut in_loop = false;
+            let mut prev_curve = ' ';
+            for (x, (&cell, &c)) in row.iter().zip(chars).enumerate() {
+                if cell == Cell::Loop {
+                    if (prev_curve == 'L' && c == '7')
+                        || (prev_curve == 'F' && c == 'J')
+                        || c == '|'
+                        || c == 'S'
+                    {
+                        in_loop = !in_loop;
+                        prev_curve = ' ';
+                    }
+                    if (prev_curve == 'L' && c == 'J') || (prev_curve == 'F' && c == '7') {
+                        prev_curve = ' ';
+                    }
+                    if c == 'L' || c == 'F' {
+                        prev_curve = c;
+                    }
+                } else if (cell == Cell::Empty || cell == Cell::Pipe) && in_loop {
+                    enclosed[(y, x)] = true;
+                }
+            }
+            if prev_curve != ' ' {
+                bail!("loop still open at the end of row {}", y + 1);
+            }
+        }
+        Ok(enclosed)
+    }
+}
@@ -0,0 +1,109 @@
+use std::str::FromStr;
+
+use aoc::generate::Rng;
+use aoc::problem::{Answer, AoCProblem};
+use aoc::utils::grid::Grid;
+use anyhow::{Error, Result};
+
+#[derive(Debug, Default)]
+pub struct AoCDay11 {
+    num_galaxies_y: Vec<usize>,
+    num_galaxies_x: Vec<usize>,
+    galaxies: Vec::<(usize, usize)>,
+}
+
+impl FromStr for AoCDay11 {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let galaxy = Grid::parse(input, |c| match c {
+            '.' => Some(false),
+            '#' => Some(true),
+            _ => None,
+        })?;
+
+        let num_galaxies_y = galaxy
+            .rows()
+            .map(|row| row.iter().filter(|&&e| e).count())
+            .collect();
+        let num_galaxies_x = galaxy
+            .columns()
+            .map(|col| col.filter(|&&e| e).count())
+            .collect();
+
+        let galaxies = galaxy
+            .iter()
+            .filter(|&(_, &galaxy)| galaxy)
+            .map(|(position, _)| position)
+            .collect();
+
+        Ok(Self { num_galaxies_y, num_galaxies_x, galaxies })
+    }
+}
+
+impl AoCDay11 {
+
+    fn dist(&self, &(y1, x1): &(usize, usize), &(y2, x2): &(usize, usize), multiplier: u64) -> u64 {
+        if x1 > x2 {
+            self.dist(&(y1, x2), &(y2, x1), multiplier)
+        } else {
+            let mut dist: u64 = 0;
+            for x in x1..x2 {
+                if self.num_galaxies_x[x] == 0 {
+                    dist += multiplier;
+                } else {
+                    dist += 1;
+                }
+            }
+            for y in y1..y2 {
+                if self.num_galaxies_y[y] == 0 {
+                    dist += multiplier;
+                } else {
+                    dist += 1;
+                }
+            }
+            dist
+        }
+    }
+
+    fn solve(&self, multiplier: u64) -> u64 {
+        let mut result = 0;
+        for (i, a) in self.galaxies.iter().enumerate() {
+            for b in self.galaxies.iter().skip(i) {
+                result += self.dist(a, b, multiplier);
+            }
+        }
+
+        result
+    }
+}
+
+impl AoCProblem for AoCDay11 {
+    const GENERATES: bool = true;
+
+    fn solve_part1(&self) -> Result<Answer> {
+        Ok(self.solve(2).into())
+    }
+
+    fn solve_part2(&self) -> Result<Answer> {
+        Ok(self.solve(1_000_000).into())
+    }
+
+    fn generate_input(size: usize, rng: &mut Rng) -> String {
+        // a square image with about 2 galaxies per row, and some empty rows
+        // and columns to expand
+        let empty_rows: Vec<bool> = (0..size).map(|_| rng.chance(0.1)).collect();
+        let empty_columns: Vec<bool> = (0..size).map(|_| rng.chance(0.1)).collect();
+        let probability = 2.0 / size as f64;
+
+        let mut input = String::new();
+        for &empty_row in &empty_rows {
+            for &empty_column in &empty_columns {
+                let galaxy = !empty_row && !empty_column && rng.chance(probability);
+                input.push(if galaxy { '#' } else { '.' });
+            }
+            input.push('\n');
+        }
+        input
+    }
+}
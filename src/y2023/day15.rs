@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use crate::problem::AoCProblem;
+use aoc::problem::{Answer, AoCProblem};
 use anyhow::{bail, Error, Result};
 
 #[derive(Debug, Default)]
@@ -15,11 +15,11 @@ impl FromStr for AoCDay15 {
 }
 
 impl AoCProblem for AoCDay15 {
-    fn solve_part1(&self) -> Result<String> {
+    fn solve_part1(&self) -> Result<Answer> {
         bail!("not implemented")
     }
 
-    fn solve_part2(&self) -> Result<String> {
+    fn solve_part2(&self) -> Result<Answer> {
         bail!("not implemented")
     }
 }